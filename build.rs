@@ -0,0 +1,27 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // The header is only regenerated on request, so that plain builds never write to the
+    // source tree or need cbindgen
+    #[cfg(feature = "ffi-header")]
+    header::generate();
+}
+
+#[cfg(feature = "ffi-header")]
+mod header {
+    use std::env;
+    use std::path::Path;
+
+    /// Writes the C header for `src/ffi.rs` to `include/aoc.h`.
+    pub fn generate() {
+        let crate_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set");
+        let crate_dir = Path::new(&crate_dir);
+
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+
+        cbindgen::generate(crate_dir)
+            .expect("could not generate C bindings")
+            .write_to_file(crate_dir.join("include").join("aoc.h"));
+    }
+}
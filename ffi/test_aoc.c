@@ -0,0 +1,75 @@
+/* Exercises the C API in include/aoc.h against the puzzle examples. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static int failures = 0;
+
+static void expect_answer(uint32_t day, uint32_t part, const char *input, const char *want) {
+    char out[64];
+    size_t out_len = sizeof(out);
+    int32_t code = aoc_solve(day, part, (const uint8_t *)input, strlen(input), (uint8_t *)out, &out_len);
+
+    if (code != AOC_OK) {
+        printf("FAIL day %u part %u: %s\n", day, part, aoc_strerror(code));
+        failures++;
+    } else if (strcmp(out, want) != 0 || out_len != strlen(want)) {
+        printf("FAIL day %u part %u: got %s, want %s\n", day, part, out, want);
+        failures++;
+    } else {
+        printf("ok   day %u part %u: %s\n", day, part, out);
+    }
+}
+
+static void expect_code(const char *name, int32_t got, int32_t want) {
+    if (got != want) {
+        printf("FAIL %s: got %s, want %s\n", name, aoc_strerror(got), aoc_strerror(want));
+        failures++;
+    } else {
+        printf("ok   %s: %s\n", name, aoc_strerror(got));
+    }
+}
+
+int main(void) {
+    const char *depths = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+    const char *directions = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n";
+    char out[64];
+    size_t out_len;
+
+    expect_answer(1, 1, depths, "7");
+    expect_answer(1, 2, depths, "5");
+    expect_answer(2, 1, directions, "150");
+    expect_answer(2, 2, directions, "900");
+
+    out_len = sizeof(out);
+    expect_code("unknown day", aoc_solve(99, 1, (const uint8_t *)depths, strlen(depths), (uint8_t *)out, &out_len), AOC_ERR_UNKNOWN_DAY);
+
+    out_len = sizeof(out);
+    expect_code("unknown year", aoc_solve_year(1999, 1, 1, (const uint8_t *)depths, strlen(depths), (uint8_t *)out, &out_len), AOC_ERR_UNKNOWN_YEAR);
+
+    out_len = sizeof(out);
+    expect_code("explicit year", aoc_solve_year(2021, 1, 1, (const uint8_t *)depths, strlen(depths), (uint8_t *)out, &out_len), AOC_OK);
+
+    out_len = sizeof(out);
+    expect_code("unknown part", aoc_solve(1, 3, (const uint8_t *)depths, strlen(depths), (uint8_t *)out, &out_len), AOC_ERR_UNKNOWN_PART);
+
+    out_len = sizeof(out);
+    expect_code("invalid input", aoc_solve(1, 1, (const uint8_t *)"deep\n", 5, (uint8_t *)out, &out_len), AOC_ERR_INVALID_INPUT);
+
+    out_len = sizeof(out);
+    expect_code("invalid utf-8", aoc_solve(1, 1, (const uint8_t *)"\xff\n", 2, (uint8_t *)out, &out_len), AOC_ERR_INVALID_UTF8);
+
+    out_len = sizeof(out);
+    expect_code("null pointer", aoc_solve(1, 1, NULL, 4, (uint8_t *)out, &out_len), AOC_ERR_NULL_POINTER);
+
+    out_len = 2;
+    expect_code("buffer too small", aoc_solve(2, 2, (const uint8_t *)directions, strlen(directions), (uint8_t *)out, &out_len), AOC_ERR_BUFFER_TOO_SMALL);
+    if (out_len != 4) {
+        printf("FAIL buffer too small: got required size %zu, want 4\n", out_len);
+        failures++;
+    }
+
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,106 @@
+/* Advent of Code solvers. Generated by cbindgen from src/ffi.rs, do not edit. */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * The answer was written to the output buffer.
+ */
+#define AOC_OK 0
+
+/**
+ * A required pointer argument was null.
+ */
+#define AOC_ERR_NULL_POINTER 1
+
+/**
+ * The input is not valid UTF-8.
+ */
+#define AOC_ERR_INVALID_UTF8 2
+
+/**
+ * The day has not been solved.
+ */
+#define AOC_ERR_UNKNOWN_DAY 3
+
+/**
+ * The part is not `1` or `2`.
+ */
+#define AOC_ERR_UNKNOWN_PART 4
+
+/**
+ * The solver rejected the input.
+ */
+#define AOC_ERR_INVALID_INPUT 5
+
+/**
+ * The output buffer is too small. The required size has been written to `out_len`.
+ */
+#define AOC_ERR_BUFFER_TOO_SMALL 6
+
+/**
+ * The solver panicked.
+ */
+#define AOC_ERR_PANIC 7
+
+/**
+ * The year has no solutions.
+ */
+#define AOC_ERR_UNKNOWN_YEAR 8
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Solves `part` of the 2021 puzzle for `day`. Equivalent to `aoc_solve_year` with a
+ * `year` of `2021`.
+ *
+ * # Safety
+ *
+ * See `aoc_solve_year`.
+ */
+int32_t aoc_solve(uint32_t day,
+                  uint32_t part,
+                  const uint8_t *input_ptr,
+                  size_t input_len,
+                  uint8_t *out_buf,
+                  size_t *out_len);
+
+/**
+ * Solves `part` of the puzzle for `day` of the event in `year`, reading `input_len` bytes
+ * of UTF-8 puzzle input from `input_ptr`.
+ *
+ * On entry `out_len` holds the capacity of `out_buf` in bytes. On success, the answer is
+ * written to `out_buf` followed by a NUL terminator, `out_len` is set to the length of the
+ * answer excluding the terminator, and `AOC_OK` is returned. If `out_buf` is too small,
+ * `out_len` is set to the capacity required and `AOC_ERR_BUFFER_TOO_SMALL` is returned.
+ * Any other failure returns one of the `AOC_ERR_*` codes and leaves `out_len` unchanged.
+ *
+ * # Safety
+ *
+ * `input_ptr` must point to `input_len` readable bytes, or may be null if `input_len` is `0`.
+ * `out_len` must point to a valid `size_t`, and `out_buf` must point to at least `*out_len`
+ * writable bytes.
+ */
+int32_t aoc_solve_year(uint32_t year,
+                       uint32_t day,
+                       uint32_t part,
+                       const uint8_t *input_ptr,
+                       size_t input_len,
+                       uint8_t *out_buf,
+                       size_t *out_len);
+
+/**
+ * Returns a static, NUL-terminated description of the error `code`.
+ */
+const char *aoc_strerror(int32_t code);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* AOC_H */
@@ -0,0 +1,34 @@
+//! Answers recorded the first time a puzzle part is solved, so that later runs can tell
+//! when a change to a solution alters its answer.
+
+use crate::input;
+use crate::Puzzle;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Returns the recorded answer to `part` of `puzzle`, if any.
+pub fn recorded(puzzle: Puzzle, part: u8) -> io::Result<Option<String>> {
+    match fs::read_to_string(answer_path(puzzle, part)?) {
+        Ok(answer) => Ok(Some(answer)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Records `answer` as the answer to `part` of `puzzle`, replacing any recorded answer.
+pub fn record(puzzle: Puzzle, part: u8, answer: &str) -> io::Result<()> {
+    let path = answer_path(puzzle, part)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, answer)
+}
+
+/// Returns the path of the recorded answer to `part` of `puzzle`, which lives next to the
+/// cached input.
+fn answer_path(puzzle: Puzzle, part: u8) -> io::Result<PathBuf> {
+    Ok(input::cache_dir()?
+        .join(puzzle.year.to_string())
+        .join(format!("day_{}.part_{}.answer", puzzle.day, part)))
+}
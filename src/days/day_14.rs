@@ -1,3 +0,0 @@
-pub fn main() {
-    println!("### day 14 ###");
-}
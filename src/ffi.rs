@@ -0,0 +1,151 @@
+//! C ABI for calling the puzzle solvers in process from non-Rust tools.
+//!
+//! The matching C header, `include/aoc.h`, is generated from this module by cbindgen when
+//! the crate is built with the `ffi-header` feature, as `make ffi-header` does.
+
+use crate::{Puzzle, YEARS};
+use std::ffi::{c_char, CStr};
+use std::io;
+use std::panic;
+use std::ptr;
+use std::slice;
+use std::str;
+
+/// The answer was written to the output buffer.
+pub const AOC_OK: i32 = 0;
+/// A required pointer argument was null.
+pub const AOC_ERR_NULL_POINTER: i32 = 1;
+/// The input is not valid UTF-8.
+pub const AOC_ERR_INVALID_UTF8: i32 = 2;
+/// The day has not been solved.
+pub const AOC_ERR_UNKNOWN_DAY: i32 = 3;
+/// The part is not `1` or `2`.
+pub const AOC_ERR_UNKNOWN_PART: i32 = 4;
+/// The solver rejected the input.
+pub const AOC_ERR_INVALID_INPUT: i32 = 5;
+/// The output buffer is too small. The required size has been written to `out_len`.
+pub const AOC_ERR_BUFFER_TOO_SMALL: i32 = 6;
+/// The solver panicked.
+pub const AOC_ERR_PANIC: i32 = 7;
+/// The year has no solutions.
+pub const AOC_ERR_UNKNOWN_YEAR: i32 = 8;
+
+/// The year solved by `aoc_solve`.
+const AOC_SOLVE_YEAR: u32 = 2021;
+
+/// Solves `part` of the 2021 puzzle for `day`. Equivalent to `aoc_solve_year` with a
+/// `year` of `2021`.
+///
+/// # Safety
+///
+/// See `aoc_solve_year`.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    part: u32,
+    input_ptr: *const u8,
+    input_len: usize,
+    out_buf: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    aoc_solve_year(
+        AOC_SOLVE_YEAR,
+        day,
+        part,
+        input_ptr,
+        input_len,
+        out_buf,
+        out_len,
+    )
+}
+
+/// Solves `part` of the puzzle for `day` of the event in `year`, reading `input_len` bytes
+/// of UTF-8 puzzle input from `input_ptr`.
+///
+/// On entry `out_len` holds the capacity of `out_buf` in bytes. On success, the answer is
+/// written to `out_buf` followed by a NUL terminator, `out_len` is set to the length of the
+/// answer excluding the terminator, and `AOC_OK` is returned. If `out_buf` is too small,
+/// `out_len` is set to the capacity required and `AOC_ERR_BUFFER_TOO_SMALL` is returned.
+/// Any other failure returns one of the `AOC_ERR_*` codes and leaves `out_len` unchanged.
+///
+/// # Safety
+///
+/// `input_ptr` must point to `input_len` readable bytes, or may be null if `input_len` is `0`.
+/// `out_len` must point to a valid `size_t`, and `out_buf` must point to at least `*out_len`
+/// writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve_year(
+    year: u32,
+    day: u32,
+    part: u32,
+    input_ptr: *const u8,
+    input_len: usize,
+    out_buf: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_buf.is_null() || out_len.is_null() || (input_ptr.is_null() && input_len > 0) {
+        return AOC_ERR_NULL_POINTER;
+    }
+
+    let input = if input_len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(input_ptr, input_len)
+    };
+    let input = match str::from_utf8(input) {
+        Ok(input) => input,
+        Err(_) => return AOC_ERR_INVALID_UTF8,
+    };
+
+    let year = match u16::try_from(year) {
+        Ok(year) if YEARS.contains(&year) => year,
+        _ => return AOC_ERR_UNKNOWN_YEAR,
+    };
+    let solver = match u8::try_from(day)
+        .ok()
+        .and_then(|day| crate::solver(Puzzle::new(year, day)))
+    {
+        Some(solver) => solver,
+        None => return AOC_ERR_UNKNOWN_DAY,
+    };
+    let part = match u8::try_from(part) {
+        Ok(part @ (1 | 2)) => part,
+        _ => return AOC_ERR_UNKNOWN_PART,
+    };
+
+    let answer = match panic::catch_unwind(|| solver(input, part)) {
+        Ok(Ok(answer)) => answer,
+        Ok(Err(e)) if e.kind() == io::ErrorKind::InvalidInput => return AOC_ERR_UNKNOWN_PART,
+        Ok(Err(_)) => return AOC_ERR_INVALID_INPUT,
+        Err(_) => return AOC_ERR_PANIC,
+    };
+
+    if answer.len() >= *out_len {
+        *out_len = answer.len() + 1;
+        return AOC_ERR_BUFFER_TOO_SMALL;
+    }
+
+    ptr::copy_nonoverlapping(answer.as_ptr(), out_buf, answer.len());
+    *out_buf.add(answer.len()) = 0;
+    *out_len = answer.len();
+
+    AOC_OK
+}
+
+/// Returns a static, NUL-terminated description of the error `code`.
+#[no_mangle]
+pub extern "C" fn aoc_strerror(code: i32) -> *const c_char {
+    let message: &'static CStr = match code {
+        AOC_OK => c"ok",
+        AOC_ERR_NULL_POINTER => c"null pointer argument",
+        AOC_ERR_INVALID_UTF8 => c"input is not valid UTF-8",
+        AOC_ERR_UNKNOWN_DAY => c"unknown day",
+        AOC_ERR_UNKNOWN_PART => c"unknown part",
+        AOC_ERR_INVALID_INPUT => c"invalid puzzle input",
+        AOC_ERR_BUFFER_TOO_SMALL => c"output buffer too small",
+        AOC_ERR_PANIC => c"solver panicked",
+        AOC_ERR_UNKNOWN_YEAR => c"unknown year",
+        _ => c"unknown error code",
+    };
+    message.as_ptr()
+}
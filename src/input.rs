@@ -0,0 +1,131 @@
+//! Puzzle inputs, downloaded from the Advent of Code website and cached per user.
+
+use crate::Puzzle;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The default base URL of the Advent of Code website.
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable holding the session token used to download inputs.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Environment variable overriding the base URL inputs are downloaded from.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// Environment variable overriding the directory inputs are cached in.
+pub const CACHE_DIR_VAR: &str = "AOC_CACHE_DIR";
+
+/// Downloads puzzle inputs into a local cache.
+#[derive(Debug)]
+pub struct Fetcher {
+    base_url: String,
+    session: String,
+    cache_dir: PathBuf,
+}
+
+impl Fetcher {
+    /// Constructs a `Fetcher` that downloads inputs from `base_url` with the `session` token
+    /// and caches them under `cache_dir`.
+    pub fn new(base_url: &str, session: &str, cache_dir: &Path) -> Fetcher {
+        Fetcher {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.to_owned(),
+            cache_dir: cache_dir.to_owned(),
+        }
+    }
+
+    /// Constructs a `Fetcher` from the `AOC_SESSION`, `AOC_BASE_URL` and `AOC_CACHE_DIR`
+    /// environment variables. Only the session token is required.
+    pub fn from_env() -> io::Result<Fetcher> {
+        let session = env::var(SESSION_VAR).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} is not set", SESSION_VAR),
+            )
+        })?;
+        let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned());
+
+        Ok(Fetcher::new(&base_url, &session, &cache_dir()?))
+    }
+
+    /// Returns the path of the cached input for `puzzle`, downloading it first if it is not
+    /// already cached.
+    pub fn fetch(&self, puzzle: Puzzle) -> io::Result<PathBuf> {
+        let path = cache_path(&self.cache_dir, puzzle);
+        if path.is_file() {
+            return Ok(path);
+        }
+
+        let url = format!("{}/{}/day/{}/input", self.base_url, puzzle.year, puzzle.day);
+        let contents = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|e| io::Error::other(format!("could not download {}: {}", url, e)))?
+            .into_string()?;
+
+        // Write to a temporary file first, so an interrupted download is never mistaken
+        // for a cached input
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let partial = path.with_extension("part");
+        fs::write(&partial, contents)?;
+        fs::rename(&partial, &path)?;
+
+        Ok(path)
+    }
+}
+
+/// Returns the directory inputs are cached in. This is `AOC_CACHE_DIR` if set, else
+/// `advent_of_code` under the user's cache directory.
+pub fn cache_dir() -> io::Result<PathBuf> {
+    if let Some(dir) = env::var_os(CACHE_DIR_VAR) {
+        return Ok(PathBuf::from(dir));
+    }
+
+    let base = match env::var_os("XDG_CACHE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => env::var_os("HOME")
+            .map(|home| Path::new(&home).join(".cache"))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "HOME is not set"))?,
+    };
+
+    Ok(base.join("advent_of_code"))
+}
+
+/// Reads the input for `puzzle`. An input file `YEAR/day_N.txt` or `day_N.txt` in the
+/// working directory takes precedence over the cached input.
+pub fn read(puzzle: Puzzle) -> io::Result<String> {
+    let file_name = format!("day_{}.txt", puzzle.day);
+    for local in [
+        Path::new(&puzzle.year.to_string()).join(&file_name),
+        PathBuf::from(&file_name),
+    ] {
+        if local.is_file() {
+            return fs::read_to_string(local);
+        }
+    }
+
+    let cached = cache_path(&cache_dir()?, puzzle);
+    fs::read_to_string(&cached).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!(
+                "could not read input for {} from the working directory or {}: {}",
+                puzzle,
+                cached.display(),
+                e
+            ),
+        )
+    })
+}
+
+/// Returns the path of the cached input for `puzzle` under `cache_dir`.
+fn cache_path(cache_dir: &Path, puzzle: Puzzle) -> PathBuf {
+    cache_dir
+        .join(puzzle.year.to_string())
+        .join(format!("day_{}.txt", puzzle.day))
+}
@@ -1 +1,54 @@
-pub mod days;
+pub mod answers;
+pub mod ffi;
+pub mod input;
+pub mod profile;
+pub mod util;
+pub mod year_2021;
+
+use std::fmt;
+use std::io;
+
+/// The years with puzzle solutions, oldest first.
+pub const YEARS: &[u16] = &[2021];
+
+/// A puzzle, identified by the year of its event and its day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+}
+
+impl Puzzle {
+    /// Constructs the `Puzzle` for `day` of the event in `year`.
+    pub fn new(year: u16, day: u8) -> Puzzle {
+        Puzzle { year, day }
+    }
+
+    /// Returns the number of parts of the puzzle. The puzzle on the last day of an event
+    /// has a single part.
+    pub fn parts(&self) -> u8 {
+        if self.day == 25 {
+            1
+        } else {
+            2
+        }
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+/// Solves one part of a day's puzzle. Takes the puzzle input and the part (`1` or `2`),
+/// and returns the answer.
+pub type Solver = fn(&str, u8) -> io::Result<String>;
+
+/// Returns the solver for `puzzle`, or `None` if `puzzle` has not been solved.
+pub fn solver(puzzle: Puzzle) -> Option<Solver> {
+    match puzzle.year {
+        2021 => year_2021::solver(puzzle.day),
+        _ => None,
+    }
+}
@@ -1,18 +1,123 @@
-use advent_of_code_2021::days;
+use advent_of_code::input::{self, Fetcher};
+use advent_of_code::profile::Profile;
+use advent_of_code::{answers, Puzzle, YEARS};
+use std::env;
+use std::io;
+use std::process;
+
+#[cfg(feature = "profile-mem")]
+#[global_allocator]
+static ALLOCATOR: advent_of_code::profile::CountingAllocator =
+    advent_of_code::profile::CountingAllocator;
+
+/// Command line usage.
+const USAGE: &str = "usage: advent_of_code [--year <year>] [--profile-mem] [run] <day>
+       advent_of_code [--year <year>] fetch <day>
+
+The year defaults to the latest year with solutions. --profile-mem reports the memory
+used by each part, and needs the profile-mem feature.";
 
 fn main() {
-    // days::day_1::main();
-    // days::day_2::main();
-    // days::day_3::main();
-    // days::day_4::main();
-    // days::day_5::main();
-    // days::day_6::main();
-    // days::day_7::main();
-    // days::day_8::main();
-    // days::day_9::main();
-    // days::day_10::main();
-    // days::day_11::main();
-    // days::day_12::main();
-    // days::day_13::main();
-    days::day_14::main();
+    let mut year = None;
+    let mut profile_mem = false;
+    let mut positional = vec![];
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => year = Some(args.next().unwrap_or_default()),
+            "--profile-mem" => profile_mem = true,
+            _ => positional.push(arg),
+        }
+    }
+    let positional: Vec<&str> = positional.iter().map(String::as_str).collect();
+
+    if profile_mem && !cfg!(feature = "profile-mem") {
+        eprintln!("error: --profile-mem needs a build with `--features profile-mem`");
+        process::exit(2);
+    }
+
+    let result = match positional.as_slice() {
+        ["fetch", day] => parse_puzzle(year.as_deref(), day).and_then(fetch),
+        ["run", day] | [day] => {
+            parse_puzzle(year.as_deref(), day).and_then(|puzzle| run(puzzle, profile_mem))
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+/// Parses a puzzle from the command line arguments `year` and `day`.
+fn parse_puzzle(year: Option<&str>, day: &str) -> io::Result<Puzzle> {
+    let year = match year {
+        None => *YEARS.last().unwrap(),
+        Some(year) => match year.parse::<u16>() {
+            Ok(year @ 2015..) => year,
+            _ => return Err(invalid_argument("year", year)),
+        },
+    };
+    let day = match day.parse::<u8>() {
+        Ok(day @ 1..=25) => day,
+        _ => return Err(invalid_argument("day", day)),
+    };
+    Ok(Puzzle::new(year, day))
+}
+
+/// Returns the error for a command line argument `value` that is not a valid `name`.
+fn invalid_argument(name: &str, value: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("invalid {}: {}", name, value),
+    )
+}
+
+/// Solves every part of `puzzle`, recording the answers the first time they are found and
+/// flagging answers that differ from the recorded ones. If `profile_mem` is `true`, also
+/// reports the memory used to solve each part.
+fn run(puzzle: Puzzle, profile_mem: bool) -> io::Result<()> {
+    let solver = advent_of_code::solver(puzzle).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("{} is not solved", puzzle),
+        )
+    })?;
+    let input = input::read(puzzle)?;
+
+    println!("### {} ###", puzzle);
+    for part in 1..=puzzle.parts() {
+        let profile = Profile::start();
+        let answer = solver(&input, part)?;
+        let stats = profile.stop();
+
+        match answers::recorded(puzzle, part)? {
+            Some(recorded) if recorded != answer => {
+                println!("part {}: {} (recorded answer: {})", part, answer, recorded)
+            }
+            Some(_) => println!("part {}: {}", part, answer),
+            None => {
+                answers::record(puzzle, part, &answer)?;
+                println!("part {}: {}", part, answer);
+            }
+        }
+
+        if profile_mem {
+            println!("part {} memory: {}", part, stats);
+        }
+    }
+
+    Ok(())
+}
+
+/// Downloads the input for `puzzle` into the cache, unless it is already cached.
+fn fetch(puzzle: Puzzle) -> io::Result<()> {
+    let path = Fetcher::from_env()?.fetch(puzzle)?;
+    println!("{} input: {}", puzzle, path.display());
+    Ok(())
 }
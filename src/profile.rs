@@ -0,0 +1,115 @@
+//! Memory profiling with a counting global allocator.
+//!
+//! Counting is opt-in: a binary must register `CountingAllocator` as its
+//! `#[global_allocator]`, which the runner does when built with the `profile-mem` feature.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Bytes currently allocated.
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+/// Most bytes allocated at once since the last reset.
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+/// Allocations made since the last reset.
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+/// Bytes allocated since the last reset.
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+/// A global allocator that wraps the system allocator and counts allocations.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    /// Records an allocation of `size` bytes.
+    fn allocated(size: usize) {
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(size, Ordering::Relaxed);
+    }
+
+    /// Records a deallocation of `size` bytes.
+    fn deallocated(size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::deallocated(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::deallocated(layout.size());
+            Self::allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Memory usage measured between a call to `Profile::start` and `Profile::stop`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Most bytes allocated at once, beyond those already allocated at the start.
+    pub peak_bytes: usize,
+    /// Number of allocations made.
+    pub allocations: usize,
+    /// Total bytes allocated.
+    pub allocated_bytes: usize,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "peak {} bytes, {} allocations, {} bytes allocated",
+            self.peak_bytes, self.allocations, self.allocated_bytes
+        )
+    }
+}
+
+/// A memory profile in progress. Only one profile should be in progress at a time, as
+/// starting a profile resets the allocator's counters.
+#[derive(Debug)]
+pub struct Profile {
+    baseline: usize,
+}
+
+impl Profile {
+    /// Starts profiling memory usage.
+    pub fn start() -> Profile {
+        let baseline = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(baseline, Ordering::Relaxed);
+        ALLOCATIONS.store(0, Ordering::Relaxed);
+        ALLOCATED.store(0, Ordering::Relaxed);
+        Profile { baseline }
+    }
+
+    /// Stops profiling, returning the memory usage since the profile started.
+    pub fn stop(self) -> Stats {
+        Stats {
+            peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(self.baseline),
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            allocated_bytes: ALLOCATED.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! Utilities shared by the puzzle solutions of every year.
+
+use std::collections::HashMap;
+use std::io;
+
+/// Returns the error for a puzzle `part` that does not exist.
+pub fn unknown_part(part: u8) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("unknown puzzle part: {}", part),
+    )
+}
+
+/// Parses a grid of single digits from `input`, keyed by `(row, column)`.
+pub fn digit_grid(input: &str) -> io::Result<HashMap<(usize, usize), u32>> {
+    let mut grid = HashMap::with_capacity(input.len());
+
+    for (i, line) in input.lines().enumerate() {
+        for (j, c) in line.chars().enumerate() {
+            let digit = c
+                .to_digit(10)
+                .ok_or_else(|| io::Error::other(format!("could not parse digit: {}", c)))?;
+            grid.insert((i, j), digit);
+        }
+    }
+
+    Ok(grid)
+}
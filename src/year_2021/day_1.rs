@@ -0,0 +1,474 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let offset = match part {
+        1 => 1,
+        2 => 3,
+        _ => return Err(crate::util::unknown_part(part)),
+    };
+    let stream = DepthStream::from_reader(input.as_bytes(), &[offset])?;
+    Ok(stream.counts()[0].to_string())
+}
+
+/// Represents a list of depths as a `Vec<i32>`.
+pub struct Depth(Vec<i32>);
+
+impl TryFrom<&str> for Depth {
+    type Error = io::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let depths = value
+            .lines()
+            .map(|s| {
+                s.parse::<i32>()
+                    .map_err(|e| io::Error::other(format!("invalid depth: {}", e)))
+            })
+            .collect::<io::Result<Vec<i32>>>()?;
+        Ok(Depth(depths))
+    }
+}
+
+impl Depth {
+    /// Returns the number of times the depth increases between `offset` number of readings.
+    pub fn increases_v1(&self, offset: usize) -> usize {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|(i, &curr)| matches!(self.0.get(i + offset), Some(&next) if next > curr))
+            .count()
+    }
+
+    /// Returns the number of times the depth increases between `offset` number of readings.
+    pub fn increases_v2(&self, offset: usize) -> usize {
+        self.0
+            .iter()
+            .skip(offset)
+            .zip(self.0.iter())
+            .filter(|(&next, &curr)| next > curr)
+            .count()
+    }
+
+    /// Returns statistics over every `window` consecutive readings, or `None` if `window`
+    /// is `0` or there are fewer than `window` readings. Streaks and the deepest dive are
+    /// measured on the window sums, so a `window` of `1` measures the raw readings.
+    pub fn report(&self, window: usize) -> Option<DepthReport> {
+        if window == 0 || self.0.len() < window {
+            return None;
+        }
+
+        // Monotonic deques of indices whose readings may still become the window min/max
+        let mut mins: VecDeque<usize> = VecDeque::new();
+        let mut maxs: VecDeque<usize> = VecDeque::new();
+        let mut sum = 0i64;
+        let mut windows = Vec::with_capacity(self.0.len() - window + 1);
+
+        for (i, &depth) in self.0.iter().enumerate() {
+            sum += depth as i64;
+            while mins.back().is_some_and(|&j| self.0[j] >= depth) {
+                mins.pop_back();
+            }
+            mins.push_back(i);
+            while maxs.back().is_some_and(|&j| self.0[j] <= depth) {
+                maxs.pop_back();
+            }
+            maxs.push_back(i);
+
+            if i + 1 < window {
+                continue;
+            }
+            let start = i + 1 - window;
+            if i >= window {
+                sum -= self.0[i - window] as i64;
+            }
+            while mins.front().is_some_and(|&j| j < start) {
+                mins.pop_front();
+            }
+            while maxs.front().is_some_and(|&j| j < start) {
+                maxs.pop_front();
+            }
+
+            windows.push(WindowStats {
+                start,
+                sum,
+                mean: sum as f64 / window as f64,
+                min: self.0[mins[0]],
+                max: self.0[maxs[0]],
+            });
+        }
+
+        let sums: Vec<i64> = windows.iter().map(|w| w.sum).collect();
+        Some(DepthReport {
+            window,
+            longest_increase: Streak::longest(&sums, |prev, next| next > prev),
+            longest_decrease: Streak::longest(&sums, |prev, next| next < prev),
+            deepest_dive: Dive::deepest(&sums),
+            windows,
+        })
+    }
+
+    /// Returns the indices of readings `detector` flags as anomalies, in order.
+    pub fn anomalies(&self, detector: &AnomalyDetector) -> Vec<usize> {
+        let (before, after) = detector.neighbours();
+        (0..self.0.len())
+            .filter(|&i| {
+                let neighbours: Vec<f64> = (i.saturating_sub(before)
+                    ..(i + after + 1).min(self.0.len()))
+                    .filter(|&j| j != i)
+                    .map(|j| self.0[j] as f64)
+                    .collect();
+                detector.is_anomaly(self.0[i] as f64, neighbours)
+            })
+            .collect()
+    }
+
+    /// Returns the readings with the anomalies `detector` flags handled by `handling`.
+    pub fn cleaned(&self, detector: &AnomalyDetector, handling: AnomalyHandling) -> Depth {
+        let mut anomalous = vec![false; self.0.len()];
+        for i in self.anomalies(detector) {
+            anomalous[i] = true;
+        }
+
+        let depths = match handling {
+            AnomalyHandling::Filter => self
+                .0
+                .iter()
+                .zip(&anomalous)
+                .filter(|(_, &anomaly)| !anomaly)
+                .map(|(&depth, _)| depth)
+                .collect(),
+            AnomalyHandling::Interpolate => (0..self.0.len())
+                .map(|i| {
+                    if !anomalous[i] {
+                        return self.0[i];
+                    }
+                    let before = (0..i).rev().find(|&j| !anomalous[j]);
+                    let after = (i + 1..self.0.len()).find(|&j| !anomalous[j]);
+                    match (before, after) {
+                        (Some(b), Some(a)) => {
+                            let fraction = (i - b) as f64 / (a - b) as f64;
+                            let (b, a) = (self.0[b] as f64, self.0[a] as f64);
+                            (b + (a - b) * fraction).round() as i32
+                        }
+                        (Some(j), None) | (None, Some(j)) => self.0[j],
+                        (None, None) => self.0[i],
+                    }
+                })
+                .collect(),
+        };
+        Depth(depths)
+    }
+
+    /// Returns the number of times the depth increases between `offset` number of readings,
+    /// once the anomalies `detector` flags are handled by `handling`.
+    pub fn increases_without_anomalies(
+        &self,
+        offset: usize,
+        detector: &AnomalyDetector,
+        handling: AnomalyHandling,
+    ) -> usize {
+        self.cleaned(detector, handling).increases_v2(offset)
+    }
+}
+
+/// The statistic used to decide whether a reading is an anomaly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnomalyMethod {
+    /// Distance from the mean of the neighbouring readings, in standard deviations.
+    ZScore,
+    /// Distance from the median of the neighbouring readings, in median absolute
+    /// deviations, scaled to be comparable to a z-score. Unlike the mean and standard
+    /// deviation, these are not thrown off by other glitches nearby.
+    MedianAbsoluteDeviation,
+}
+
+/// What to do with anomalous readings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnomalyHandling {
+    /// Drop anomalous readings.
+    Filter,
+    /// Replace each anomalous reading by interpolating linearly between the nearest normal
+    /// readings on either side.
+    Interpolate,
+}
+
+/// Detects readings that stand out from their neighbours, such as sensor spikes and
+/// dropouts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnomalyDetector {
+    pub method: AnomalyMethod,
+    /// Number of readings around each reading, including the reading itself, that it is
+    /// compared against. The neighbours are split evenly before and after the reading; for
+    /// an even `window`, the extra neighbour comes after it.
+    pub window: usize,
+    /// Score above which a reading is an anomaly.
+    pub threshold: f64,
+    /// Smallest spread of the neighbouring readings used to score a reading. Depths are
+    /// whole numbers, so the spread of neighbours that barely vary is often `0` or `1`,
+    /// which would make a difference of a unit or two look extreme.
+    pub min_spread: f64,
+}
+
+impl Default for AnomalyDetector {
+    fn default() -> AnomalyDetector {
+        AnomalyDetector {
+            method: AnomalyMethod::MedianAbsoluteDeviation,
+            window: 7,
+            threshold: 3.5,
+            min_spread: 2.0,
+        }
+    }
+}
+
+impl AnomalyDetector {
+    /// Scale making the median absolute deviation of normally distributed readings
+    /// comparable to their standard deviation.
+    const MAD_SCALE: f64 = 0.6745;
+
+    /// Returns the number of neighbours before and after each reading that it is compared
+    /// against.
+    fn neighbours(&self) -> (usize, usize) {
+        let neighbours = self.window.saturating_sub(1);
+        (neighbours / 2, neighbours - neighbours / 2)
+    }
+
+    /// Returns whether `depth` is an anomaly among its `neighbours`.
+    fn is_anomaly(&self, depth: f64, mut neighbours: Vec<f64>) -> bool {
+        if neighbours.is_empty() {
+            return false;
+        }
+
+        let (center, spread, scale) = match self.method {
+            AnomalyMethod::ZScore => {
+                let n = neighbours.len() as f64;
+                let mean = neighbours.iter().sum::<f64>() / n;
+                let variance = neighbours.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / n;
+                (mean, variance.sqrt(), 1.0)
+            }
+            AnomalyMethod::MedianAbsoluteDeviation => {
+                let center = median(&mut neighbours);
+                let mut deviations: Vec<f64> =
+                    neighbours.iter().map(|d| (d - center).abs()).collect();
+                (center, median(&mut deviations), Self::MAD_SCALE)
+            }
+        };
+
+        let distance = (depth - center).abs();
+        scale * distance / spread.max(self.min_spread) > self.threshold
+    }
+}
+
+/// Returns the median of `values`, reordering them.
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Statistics of one window of consecutive readings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WindowStats {
+    /// Index of the first reading in the window.
+    pub start: usize,
+    pub sum: i64,
+    pub mean: f64,
+    pub min: i32,
+    pub max: i32,
+}
+
+/// A run of consecutive windows whose sums change in the same direction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Streak {
+    /// Index of the window the run starts from.
+    pub start: usize,
+    /// Number of consecutive changes in the run.
+    pub len: usize,
+}
+
+impl Streak {
+    /// Returns the longest run of consecutive pairs of `sums` for which `changes` holds,
+    /// the earliest if several are equally long.
+    fn longest(sums: &[i64], changes: impl Fn(i64, i64) -> bool) -> Streak {
+        let mut longest = Streak::default();
+        let mut current = Streak::default();
+        for (i, pair) in sums.windows(2).enumerate() {
+            if changes(pair[0], pair[1]) {
+                if current.len == 0 {
+                    current.start = i;
+                }
+                current.len += 1;
+                if current.len > longest.len {
+                    longest = current;
+                }
+            } else {
+                current.len = 0;
+            }
+        }
+        longest
+    }
+}
+
+/// The largest increase in depth from one window to a later window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Dive {
+    /// Index of the shallowest window the dive starts from.
+    pub from: usize,
+    /// Index of the deepest window the dive reaches.
+    pub to: usize,
+    /// Difference between the window sums at `to` and `from`, `0` if the depth never
+    /// increases.
+    pub depth: i64,
+}
+
+impl Dive {
+    /// Returns the deepest dive across `sums`, the earliest if several are equally deep.
+    fn deepest(sums: &[i64]) -> Dive {
+        let mut deepest = Dive::default();
+        let mut shallowest = 0;
+        for (i, &sum) in sums.iter().enumerate() {
+            if sum < sums[shallowest] {
+                shallowest = i;
+            } else if sum - sums[shallowest] > deepest.depth {
+                deepest = Dive {
+                    from: shallowest,
+                    to: i,
+                    depth: sum - sums[shallowest],
+                };
+            }
+        }
+        deepest
+    }
+}
+
+/// Statistics over every window of consecutive readings, as returned by
+/// `Depth::report`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DepthReport {
+    /// Number of readings in each window.
+    pub window: usize,
+    /// Statistics of each window, in order.
+    pub windows: Vec<WindowStats>,
+    /// Longest run of increasing window sums.
+    pub longest_increase: Streak,
+    /// Longest run of decreasing window sums.
+    pub longest_decrease: Streak,
+    /// Largest increase from a window sum to any later window sum.
+    pub deepest_dive: Dive,
+}
+
+/// Parses depths lazily from the lines of `reader`, one depth per line.
+pub fn read_depths<R: BufRead>(reader: R) -> impl Iterator<Item = io::Result<i32>> {
+    reader.lines().map(|line| {
+        line?
+            .parse::<i32>()
+            .map_err(|e| io::Error::other(format!("invalid depth: {}", e)))
+    })
+}
+
+/// Counts depth increases between readings several offsets apart over a stream of
+/// readings, without storing the whole stream. Only the last `max(offsets)` readings are
+/// kept, so arbitrarily long or live sonar feeds can be analyzed in a single pass.
+#[derive(Clone, Debug)]
+pub struct DepthStream {
+    offsets: Vec<usize>,
+    /// The most recent readings, oldest first.
+    window: VecDeque<i32>,
+    /// Number of readings kept in the window, the largest offset.
+    window_len: usize,
+    /// Number of increases so far for each offset.
+    counts: Vec<usize>,
+    readings: usize,
+}
+
+impl DepthStream {
+    /// Constructs a `DepthStream` counting increases between readings each of `offsets`
+    /// apart.
+    pub fn new(offsets: &[usize]) -> DepthStream {
+        let window_len = offsets.iter().copied().max().unwrap_or(0);
+        DepthStream {
+            offsets: offsets.to_vec(),
+            window: VecDeque::with_capacity(window_len + 1),
+            window_len,
+            counts: vec![0; offsets.len()],
+            readings: 0,
+        }
+    }
+
+    /// Analyzes every depth read from `reader` for each of `offsets`.
+    pub fn from_reader<R: BufRead>(reader: R, offsets: &[usize]) -> io::Result<DepthStream> {
+        let mut stream = DepthStream::new(offsets);
+        for depth in read_depths(reader) {
+            stream.push(depth?);
+        }
+        Ok(stream)
+    }
+
+    /// Adds the next reading, returning the running number of increases for each offset,
+    /// in the same order as the offsets.
+    pub fn push(&mut self, depth: i32) -> &[usize] {
+        for (count, &offset) in self.counts.iter_mut().zip(&self.offsets) {
+            if offset > 0
+                && offset <= self.window.len()
+                && depth > self.window[self.window.len() - offset]
+            {
+                *count += 1;
+            }
+        }
+
+        self.window.push_back(depth);
+        if self.window.len() > self.window_len {
+            self.window.pop_front();
+        }
+        self.readings += 1;
+        &self.counts
+    }
+
+    /// Returns an iterator that adds each of `depths` in turn, yielding the running number
+    /// of increases for each offset after every reading.
+    pub fn running_totals<I>(self, depths: I) -> RunningTotals<I::IntoIter>
+    where
+        I: IntoIterator<Item = i32>,
+    {
+        RunningTotals {
+            stream: self,
+            depths: depths.into_iter(),
+        }
+    }
+
+    /// Returns the offsets, in the order their counts are reported.
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    /// Returns the number of increases so far for each offset.
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    /// Returns the number of readings added so far.
+    pub fn readings(&self) -> usize {
+        self.readings
+    }
+}
+
+/// An iterator yielding the running increase counts of a `DepthStream` after each
+/// reading.
+#[derive(Clone, Debug)]
+pub struct RunningTotals<I> {
+    stream: DepthStream,
+    depths: I,
+}
+
+impl<I: Iterator<Item = i32>> Iterator for RunningTotals<I> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let depth = self.depths.next()?;
+        Some(self.stream.push(depth).to_vec())
+    }
+}
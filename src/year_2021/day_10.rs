@@ -1,20 +1,30 @@
-use std::fs;
-
-pub fn main() {
-    println!("### day 10 ###");
-
-    let input = fs::read_to_string("./day_10.txt").expect("could not read input file");
-    let chunks: Vec<&str> = input.lines().collect();
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    match part {
+        1 => Ok(syntax_error_score(input).to_string()),
+        2 => autocomplete_score(input)
+            .map(|score| score.to_string())
+            .ok_or_else(|| io::Error::other("no incomplete chunks")),
+        _ => Err(crate::util::unknown_part(part)),
+    }
+}
 
+/// Returns the total syntax error score for all chunks in `input`.
+fn syntax_error_score(input: &str) -> u32 {
     let mut syntax_error_score = 0;
-    for chunk in chunks.iter() {
+    for chunk in input.lines() {
         syntax_error_score += navigation::syntax_error_score(chunk);
     }
+    syntax_error_score
+}
 
-    println!("part 1: syntax error score = {}", syntax_error_score);
-
-    let incomplete_chunks: Vec<_> = chunks
-        .into_iter()
+/// Returns the middle autocomplete score of the incomplete chunks in `input`,
+/// or `None` if there are no incomplete chunks.
+fn autocomplete_score(input: &str) -> Option<u64> {
+    let incomplete_chunks: Vec<_> = input
+        .lines()
         .filter(|chunk| navigation::syntax_error_score(chunk) == 0)
         .collect();
 
@@ -24,10 +34,7 @@ pub fn main() {
     }
     scores.sort_unstable();
 
-    println!(
-        "part 2: autocomplete score = {}",
-        scores.get(scores.len() / 2).unwrap()
-    );
+    scores.get(scores.len() / 2).copied()
 }
 
 mod navigation {
@@ -1,29 +1,21 @@
 use octopus::Pod;
-use std::path::Path;
-
-pub fn main() {
-    println!("### day 11 ###");
-
-    let input_path = Path::new("./day_11.txt");
-    let mut pod_part_1 = Pod::from_file(input_path).expect("could not read input file");
-    let pod_part_2 = pod_part_1.clone();
-
-    // Part 1
-    println!(
-        "part 1: flashes after 100 steps = {}",
-        pod_part_1.simulate(100)
-    );
-
-    // Part 2
-    println!("part 2: steps until sync = {}", pod_part_2.sync());
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let mut pod = Pod::try_from(input)?;
+    match part {
+        1 => Ok(pod.simulate(100).to_string()),
+        2 => Ok(pod.sync().to_string()),
+        _ => Err(crate::util::unknown_part(part)),
+    }
 }
 
 mod octopus {
+    use crate::util;
     use std::collections::HashMap;
     use std::fmt;
-    use std::fs;
     use std::io;
-    use std::path::Path;
 
     /// Width/length of the octopus pod size.
     const POD_SIZE: usize = 10;
@@ -67,29 +59,20 @@ mod octopus {
     #[derive(Clone, Debug)]
     pub struct Pod(HashMap<(usize, usize), Octopus>);
 
-    impl Pod {
-        /// Constructs a `Pod` of octopus from input `path`.
-        pub fn from_file(path: &Path) -> io::Result<Pod> {
-            let contents = fs::read_to_string(path)?;
-            let mut octopus = HashMap::with_capacity(POD_SIZE * POD_SIZE);
-
-            for (i, line) in contents.lines().enumerate() {
-                for (j, digit) in line.chars().enumerate() {
-                    octopus.insert(
-                        (i, j),
-                        Octopus::new(digit.to_digit(10).ok_or_else(|| {
-                            io::Error::new(
-                                io::ErrorKind::Other,
-                                format!("invalid octopus state: {}", digit),
-                            )
-                        })?),
-                    );
-                }
-            }
+    impl TryFrom<&str> for Pod {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let octopus = util::digit_grid(value)?
+                .into_iter()
+                .map(|(position, state)| (position, Octopus::new(state)))
+                .collect();
 
             Ok(Pod(octopus))
         }
+    }
 
+    impl Pod {
         /// Simulates a `Pod` for a given number of `steps`.
         pub fn simulate(&mut self, steps: u32) -> u32 {
             let mut flashed = 0;
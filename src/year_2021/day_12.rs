@@ -1,24 +1,19 @@
 use cave::Caves;
-use std::path::Path;
-
-pub fn main() {
-    println!("### day 12 ###");
-
-    let input = Path::new("./day_12.txt");
-    let caves = Caves::from_file(input).expect("could not read input file");
-
-    // Part 1
-    println!("part 1: number of paths = {}", caves.paths(false));
-
-    // Part 2
-    println!("part 2: number of paths = {}", caves.paths(true));
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let caves = Caves::try_from(input)?;
+    match part {
+        1 => Ok(caves.paths(false).to_string()),
+        2 => Ok(caves.paths(true).to_string()),
+        _ => Err(crate::util::unknown_part(part)),
+    }
 }
 
 mod cave {
     use std::collections::HashMap;
-    use std::fs;
     use std::io;
-    use std::path::Path;
 
     /// The type of cave.
     #[derive(Debug)]
@@ -49,18 +44,18 @@ mod cave {
                     cave_type: CaveType::End,
                     connections: vec![],
                 }),
-                value if value.chars().all(|c| matches!(c, 'a'..='z')) => Ok(Cave {
+                value if value.chars().all(|c: char| c.is_ascii_lowercase()) => Ok(Cave {
                     cave_type: CaveType::Small,
                     connections: vec![],
                 }),
-                value if value.chars().all(|c| matches!(c, 'A'..='Z')) => Ok(Cave {
+                value if value.chars().all(|c: char| c.is_ascii_uppercase()) => Ok(Cave {
                     cave_type: CaveType::Big,
                     connections: vec![],
                 }),
-                _ => Err(io::Error::new(
-                    io::ErrorKind::Other,
-                    format!("cannot parse Cave from string slice '{}'", value),
-                )),
+                _ => Err(io::Error::other(format!(
+                    "cannot parse Cave from string slice '{}'",
+                    value
+                ))),
             }
         }
     }
@@ -69,15 +64,15 @@ mod cave {
     #[derive(Debug)]
     pub struct Caves(HashMap<String, Cave>);
 
-    impl Caves {
-        /// Parses an instance of `Caves` from the input file at `path`.
-        pub fn from_file(path: &Path) -> io::Result<Caves> {
+    impl TryFrom<&str> for Caves {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
             let mut caves = Caves(HashMap::new());
-            let contents = fs::read_to_string(path)?;
 
-            for line in contents.lines() {
+            for line in value.lines() {
                 let cave_pair: Vec<_> = line.splitn(2, '-').collect();
-                let &cave_1 = cave_pair.get(0).unwrap_or(&"");
+                let &cave_1 = cave_pair.first().unwrap_or(&"");
                 let &cave_2 = cave_pair.get(1).unwrap_or(&"");
 
                 caves
@@ -96,7 +91,9 @@ mod cave {
 
             Ok(caves)
         }
+    }
 
+    impl Caves {
         /// Returns the number of unique paths from the cave start to end.
         /// Big caves can be visited any number of times. Small caves can be visited once,
         /// unless `allow_one_small_cave_twice` is true, in which case a single small cave
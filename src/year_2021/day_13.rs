@@ -1,30 +1,26 @@
 use origami::Origami;
-use std::path::Path;
-
-pub fn main() {
-    println!("### day 13 ###");
-
-    let input = Path::new("./day_13.txt");
-    let mut origami = Origami::from_file(input).expect("could not read input file");
-
-    if origami.fold().is_some() {
-        println!(
-            "part 1: dots visible after first fold = {}",
-            origami.visible()
-        );
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let mut origami = Origami::try_from(input)?;
+    match part {
+        1 => {
+            origami.fold();
+            Ok(origami.visible().to_string())
+        }
+        2 => {
+            while origami.fold().is_some() {}
+            Ok(origami.to_string())
+        }
+        _ => Err(crate::util::unknown_part(part)),
     }
-
-    while origami.fold().is_some() {}
-    println!("part 2: infrared thermal imaging camera system code");
-    println!("{}", origami);
 }
 
 mod origami {
     use std::collections::{HashSet, VecDeque};
     use std::fmt;
-    use std::fs;
     use std::io;
-    use std::path::Path;
 
     /// The origami paper.
     #[derive(Debug)]
@@ -55,9 +51,10 @@ mod origami {
         instructions: VecDeque<Instruction>,
     }
 
-    impl Origami {
-        /// Constructs an `Origami` instance from input `path`.
-        pub fn from_file(path: &Path) -> io::Result<Origami> {
+    impl TryFrom<&str> for Origami {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
             let mut origami = Origami {
                 paper: Paper {
                     dots: HashSet::new(),
@@ -67,8 +64,7 @@ mod origami {
                 instructions: VecDeque::new(),
             };
 
-            let contents = fs::read_to_string(path)?;
-            for line in contents.lines() {
+            for line in value.lines() {
                 if line.starts_with("fold along") {
                     let mut pieces = line.splitn(2, '=');
 
@@ -105,7 +101,9 @@ mod origami {
 
             Ok(origami)
         }
+    }
 
+    impl Origami {
         /// Folds the `Origami` instance according to the next instruction.
         /// Returns `None` if no instructions remain, else returns `Some` containing
         /// the instruction that was executed.
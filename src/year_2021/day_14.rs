@@ -0,0 +1,134 @@
+pub use polymer::Polymer;
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let mut polymer = Polymer::try_from(input)?;
+    match part {
+        1 => polymer.grow(10),
+        2 => polymer.grow(40),
+        _ => return Err(crate::util::unknown_part(part)),
+    }
+    Ok(polymer.quantity_difference().to_string())
+}
+
+mod polymer {
+    use std::collections::{BTreeMap, HashMap};
+    use std::io;
+
+    /// A polymer, tracked as the number of each pair of adjacent elements rather than as
+    /// the full chain, so that the work per step does not grow with the polymer's length.
+    #[derive(Clone, Debug)]
+    pub struct Polymer {
+        /// Pair insertion rules, mapping a pair of adjacent elements to the element
+        /// inserted between them.
+        rules: HashMap<(char, char), char>,
+        /// Number of occurrences of each pair of adjacent elements.
+        pairs: HashMap<(char, char), u64>,
+        /// Number of occurrences of each element.
+        elements: BTreeMap<char, u64>,
+        /// Number of insertion steps run so far.
+        steps: u32,
+    }
+
+    impl TryFrom<&str> for Polymer {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let mut lines = value.lines();
+
+            let template: Vec<char> = lines
+                .next()
+                .map(|l| l.trim().chars().collect())
+                .unwrap_or_default();
+            if template.is_empty() {
+                return Err(io::Error::other("missing polymer template"));
+            }
+
+            let mut rules = HashMap::new();
+            for line in lines.filter(|l| !l.trim().is_empty()) {
+                let rule = line.split_once(" -> ").and_then(|(pair, element)| {
+                    let pair: Vec<char> = pair.trim().chars().collect();
+                    let element: Vec<char> = element.trim().chars().collect();
+                    match (pair.as_slice(), element.as_slice()) {
+                        (&[a, b], &[c]) => Some(((a, b), c)),
+                        _ => None,
+                    }
+                });
+                match rule {
+                    Some((pair, element)) => rules.insert(pair, element),
+                    None => {
+                        return Err(io::Error::other(format!(
+                            "invalid pair insertion rule: {}",
+                            line
+                        )))
+                    }
+                };
+            }
+
+            let mut pairs = HashMap::new();
+            for pair in template.windows(2) {
+                *pairs.entry((pair[0], pair[1])).or_insert(0) += 1;
+            }
+
+            let mut elements = BTreeMap::new();
+            for &element in &template {
+                *elements.entry(element).or_insert(0) += 1;
+            }
+
+            Ok(Polymer {
+                rules,
+                pairs,
+                elements,
+                steps: 0,
+            })
+        }
+    }
+
+    impl Polymer {
+        /// Runs one pair insertion step, returning the number of each element afterwards.
+        pub fn step(&mut self) -> &BTreeMap<char, u64> {
+            let mut pairs = HashMap::with_capacity(self.pairs.len() * 2);
+
+            for (&(a, b), &count) in &self.pairs {
+                match self.rules.get(&(a, b)) {
+                    Some(&c) => {
+                        *pairs.entry((a, c)).or_insert(0) += count;
+                        *pairs.entry((c, b)).or_insert(0) += count;
+                        *self.elements.entry(c).or_insert(0) += count;
+                    }
+                    None => *pairs.entry((a, b)).or_insert(0) += count,
+                }
+            }
+
+            self.pairs = pairs;
+            self.steps += 1;
+            &self.elements
+        }
+
+        /// Runs `steps` pair insertion steps.
+        pub fn grow(&mut self, steps: u32) {
+            for _ in 0..steps {
+                self.step();
+            }
+        }
+
+        /// Returns the number of each element in the polymer.
+        pub fn element_counts(&self) -> &BTreeMap<char, u64> {
+            &self.elements
+        }
+
+        /// Returns the number of pair insertion steps run so far.
+        pub fn steps(&self) -> u32 {
+            self.steps
+        }
+
+        /// Returns the quantity of the most common element minus the quantity of the least
+        /// common element.
+        pub fn quantity_difference(&self) -> u64 {
+            let max = self.elements.values().max().unwrap_or(&0);
+            let min = self.elements.values().min().unwrap_or(&0);
+            max - min
+        }
+    }
+}
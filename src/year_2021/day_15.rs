@@ -0,0 +1,176 @@
+pub use chiton::{RiskMap, Route};
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let risk_map = RiskMap::try_from(input)?;
+    let risk = match part {
+        1 => risk_map.lowest_total_risk(),
+        2 => risk_map.tiled(5).lowest_total_risk(),
+        _ => return Err(crate::util::unknown_part(part)),
+    };
+    risk.map(|r| r.to_string())
+        .ok_or_else(|| io::Error::other("no path through the cave"))
+}
+
+mod chiton {
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+    use std::io;
+
+    /// A map of the risk level of each position in a cave full of chitons.
+    #[derive(Clone, Debug)]
+    pub struct RiskMap {
+        /// Risk levels in row-major order.
+        risks: Vec<u32>,
+        width: usize,
+        height: usize,
+    }
+
+    /// A route through the cave.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Route {
+        /// Total risk of the route. The risk of the starting position is not counted,
+        /// as it is never entered.
+        pub risk: u32,
+        /// Positions visited as `(row, column)`, from the top left to the bottom right.
+        pub positions: Vec<(usize, usize)>,
+    }
+
+    impl TryFrom<&str> for RiskMap {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let mut risks = Vec::with_capacity(value.len());
+            let mut width = 0;
+            let mut height = 0;
+
+            for line in value.lines().filter(|l| !l.is_empty()) {
+                for c in line.chars() {
+                    match c.to_digit(10) {
+                        Some(risk @ 1..=9) => risks.push(risk),
+                        _ => return Err(io::Error::other(format!("invalid risk level: {}", c))),
+                    }
+                }
+
+                if height == 0 {
+                    width = risks.len();
+                } else if risks.len() != width * (height + 1) {
+                    return Err(io::Error::other(format!(
+                        "row {} does not have width {}",
+                        height, width
+                    )));
+                }
+                height += 1;
+            }
+
+            Ok(RiskMap {
+                risks,
+                width,
+                height,
+            })
+        }
+    }
+
+    impl RiskMap {
+        /// Returns the risk map tiled `times` times in each direction. Each tile's risk
+        /// levels are one higher than the tile above or to its left, wrapping from `9`
+        /// back around to `1`.
+        pub fn tiled(&self, times: usize) -> RiskMap {
+            let width = self.width * times;
+            let height = self.height * times;
+            let mut risks = Vec::with_capacity(width * height);
+
+            for row in 0..height {
+                for column in 0..width {
+                    let risk = self.risks[(row % self.height) * self.width + column % self.width];
+                    let increase = (row / self.height + column / self.width) as u32;
+                    risks.push((risk + increase - 1) % 9 + 1);
+                }
+            }
+
+            RiskMap {
+                risks,
+                width,
+                height,
+            }
+        }
+
+        /// Returns the lowest total risk of any route from the top left to the bottom right,
+        /// or `None` if the map is empty.
+        pub fn lowest_total_risk(&self) -> Option<u32> {
+            self.safest_route().map(|route| route.risk)
+        }
+
+        /// Returns the route with the lowest total risk from the top left to the bottom
+        /// right, or `None` if the map is empty.
+        ///
+        /// Uses A* search, with the Manhattan distance to the bottom right as the heuristic.
+        /// As every risk level is at least `1`, the heuristic never overestimates.
+        pub fn safest_route(&self) -> Option<Route> {
+            if self.risks.is_empty() {
+                return None;
+            }
+
+            let goal = self.risks.len() - 1;
+            let mut risks = vec![u32::MAX; self.risks.len()];
+            let mut previous = vec![usize::MAX; self.risks.len()];
+            let mut queue = BinaryHeap::new();
+
+            risks[0] = 0;
+            queue.push(Reverse((self.heuristic(0), 0, 0)));
+
+            while let Some(Reverse((_, risk, index))) = queue.pop() {
+                if index == goal {
+                    break;
+                }
+                if risk > risks[index] {
+                    continue;
+                }
+
+                for next in self.neighbours(index) {
+                    let next_risk = risk + self.risks[next];
+                    if next_risk < risks[next] {
+                        risks[next] = next_risk;
+                        previous[next] = index;
+                        queue.push(Reverse((next_risk + self.heuristic(next), next_risk, next)));
+                    }
+                }
+            }
+
+            let mut positions = vec![];
+            let mut index = goal;
+            while index != usize::MAX {
+                positions.push((index / self.width, index % self.width));
+                index = previous[index];
+            }
+            positions.reverse();
+
+            Some(Route {
+                risk: risks[goal],
+                positions,
+            })
+        }
+
+        /// Returns the Manhattan distance from the position at `index` to the bottom right.
+        fn heuristic(&self, index: usize) -> u32 {
+            let (row, column) = (index / self.width, index % self.width);
+            (self.height - 1 - row + self.width - 1 - column) as u32
+        }
+
+        /// Returns the indices of the positions above, below, left and right of `index`.
+        fn neighbours(&self, index: usize) -> impl Iterator<Item = usize> {
+            let (row, column) = (index / self.width, index % self.width);
+            let width = self.width;
+
+            [
+                (row > 0).then(|| index - width),
+                (row + 1 < self.height).then(|| index + width),
+                (column > 0).then(|| index - 1),
+                (column + 1 < width).then(|| index + 1),
+            ]
+            .into_iter()
+            .flatten()
+        }
+    }
+}
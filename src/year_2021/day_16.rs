@@ -0,0 +1,327 @@
+pub use packet::{Operator, Packet, PacketError, Payload};
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let packet = Packet::from_hex(input).map_err(io::Error::other)?;
+    match part {
+        1 => Ok(packet.version_sum().to_string()),
+        2 => Ok(packet.evaluate().map_err(io::Error::other)?.to_string()),
+        _ => Err(crate::util::unknown_part(part)),
+    }
+}
+
+mod packet {
+    use std::error::Error;
+    use std::fmt;
+
+    /// Packet type ID of a literal value packet.
+    const LITERAL_TYPE_ID: u64 = 4;
+
+    /// An error decoding or evaluating a BITS transmission.
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum PacketError {
+        /// The transmission contains a character that is not a hexadecimal digit.
+        InvalidHex(char),
+        /// The transmission ended while `needed` more bits were expected at bit `position`.
+        Truncated { position: usize, needed: usize },
+        /// An operator packet with a comparison type has other than two sub-packets, or
+        /// another operator packet has none.
+        InvalidOperandCount { operator: Operator, count: usize },
+        /// A length type 0 operator's sub-packets do not fill exactly its declared length.
+        LengthMismatch { expected: usize, actual: usize },
+        /// The literal value starting at bit `position` does not fit in 64 bits.
+        LiteralTooLarge { position: usize },
+        /// The value of an operator packet does not fit in 64 bits.
+        Overflow(Operator),
+    }
+
+    impl fmt::Display for PacketError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                PacketError::InvalidHex(c) => write!(f, "invalid hexadecimal digit: {:?}", c),
+                PacketError::Truncated { position, needed } => write!(
+                    f,
+                    "transmission truncated: {} more bits needed at bit {}",
+                    needed, position
+                ),
+                PacketError::InvalidOperandCount { operator, count } => write!(
+                    f,
+                    "{} packet needs {} sub-packets, found {}",
+                    operator,
+                    if operator.is_comparison() {
+                        "two"
+                    } else {
+                        "one or more"
+                    },
+                    count
+                ),
+                PacketError::LengthMismatch { expected, actual } => write!(
+                    f,
+                    "sub-packets span {} bits, but the operator declares {}",
+                    actual, expected
+                ),
+                PacketError::LiteralTooLarge { position } => {
+                    write!(f, "literal at bit {} does not fit in 64 bits", position)
+                }
+                PacketError::Overflow(operator) => {
+                    write!(f, "{} packet value does not fit in 64 bits", operator)
+                }
+            }
+        }
+    }
+
+    impl Error for PacketError {}
+
+    /// The operation performed by an operator packet on the values of its sub-packets.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Operator {
+        Sum,
+        Product,
+        Minimum,
+        Maximum,
+        GreaterThan,
+        LessThan,
+        EqualTo,
+    }
+
+    impl Operator {
+        /// Returns the operator for packet type ID `type_id`, or `None` if `type_id` is not
+        /// an operator type ID.
+        fn from_type_id(type_id: u64) -> Option<Operator> {
+            match type_id {
+                0 => Some(Operator::Sum),
+                1 => Some(Operator::Product),
+                2 => Some(Operator::Minimum),
+                3 => Some(Operator::Maximum),
+                5 => Some(Operator::GreaterThan),
+                6 => Some(Operator::LessThan),
+                7 => Some(Operator::EqualTo),
+                _ => None,
+            }
+        }
+
+        /// Returns an error unless `count` sub-packets suit the operator: exactly two for a
+        /// comparison, and at least one otherwise.
+        fn check_operands(self, count: usize) -> Result<(), PacketError> {
+            let valid = if self.is_comparison() {
+                count == 2
+            } else {
+                count > 0
+            };
+            if !valid {
+                return Err(PacketError::InvalidOperandCount {
+                    operator: self,
+                    count,
+                });
+            }
+            Ok(())
+        }
+
+        /// Returns whether the operator compares exactly two sub-packets.
+        fn is_comparison(self) -> bool {
+            matches!(
+                self,
+                Operator::GreaterThan | Operator::LessThan | Operator::EqualTo
+            )
+        }
+    }
+
+    impl fmt::Display for Operator {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let name = match self {
+                Operator::Sum => "sum",
+                Operator::Product => "product",
+                Operator::Minimum => "minimum",
+                Operator::Maximum => "maximum",
+                Operator::GreaterThan => "greater than",
+                Operator::LessThan => "less than",
+                Operator::EqualTo => "equal to",
+            };
+            write!(f, "{}", name)
+        }
+    }
+
+    /// The contents of a packet.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum Payload {
+        /// A literal value.
+        Literal(u64),
+        /// An operation on the values of sub-packets.
+        Operator {
+            operator: Operator,
+            packets: Vec<Packet>,
+        },
+    }
+
+    /// A BITS packet.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Packet {
+        pub version: u8,
+        pub payload: Payload,
+    }
+
+    impl Packet {
+        /// Decodes the outermost packet of a hexadecimal BITS transmission. Surrounding
+        /// whitespace and any padding bits after the packet are ignored.
+        pub fn from_hex(hex: &str) -> Result<Packet, PacketError> {
+            let mut bits = Bits::from_hex(hex.trim())?;
+            Packet::decode(&mut bits)
+        }
+
+        /// Returns the sum of the version numbers of this packet and all its sub-packets.
+        pub fn version_sum(&self) -> u64 {
+            let sub_packets = match &self.payload {
+                Payload::Literal(_) => 0,
+                Payload::Operator { packets, .. } => packets.iter().map(Packet::version_sum).sum(),
+            };
+            self.version as u64 + sub_packets
+        }
+
+        /// Evaluates the expression represented by this packet, or returns an error if an
+        /// operator has the wrong number of sub-packets or a sum or product does not fit in
+        /// 64 bits.
+        pub fn evaluate(&self) -> Result<u64, PacketError> {
+            let (operator, packets) = match &self.payload {
+                Payload::Literal(value) => return Ok(*value),
+                Payload::Operator { operator, packets } => (*operator, packets),
+            };
+            // Decoded packets have been checked already, but packets can also be built by hand
+            operator.check_operands(packets.len())?;
+            let values = packets
+                .iter()
+                .map(Packet::evaluate)
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut values = values.into_iter();
+
+            let value = match operator {
+                Operator::Sum => values.try_fold(0u64, u64::checked_add),
+                Operator::Product => values.try_fold(1u64, u64::checked_mul),
+                Operator::Minimum => values.min(),
+                Operator::Maximum => values.max(),
+                comparison => {
+                    let (a, b) = (values.next().unwrap(), values.next().unwrap());
+                    let result = match comparison {
+                        Operator::GreaterThan => a > b,
+                        Operator::LessThan => a < b,
+                        _ => a == b,
+                    };
+                    Some(result as u64)
+                }
+            };
+            value.ok_or(PacketError::Overflow(operator))
+        }
+
+        /// Decodes the packet starting at the current position of `bits`.
+        fn decode(bits: &mut Bits) -> Result<Packet, PacketError> {
+            let version = bits.read(3)? as u8;
+            let type_id = bits.read(3)?;
+
+            if type_id == LITERAL_TYPE_ID {
+                let position = bits.position;
+                let mut value: u64 = 0;
+                loop {
+                    let group = bits.read(5)?;
+                    if value.leading_zeros() < 4 {
+                        return Err(PacketError::LiteralTooLarge { position });
+                    }
+                    value = (value << 4) | (group & 0b1111);
+                    if group & 0b10000 == 0 {
+                        break;
+                    }
+                }
+                return Ok(Packet {
+                    version,
+                    payload: Payload::Literal(value),
+                });
+            }
+
+            // Every 3-bit type ID other than the literal type ID is an operator
+            let operator = Operator::from_type_id(type_id).unwrap();
+
+            let mut packets = vec![];
+            if bits.read(1)? == 0 {
+                let length = bits.read(15)? as usize;
+                let start = bits.position;
+                while bits.position < start + length {
+                    packets.push(Packet::decode(bits)?);
+                }
+                if bits.position != start + length {
+                    return Err(PacketError::LengthMismatch {
+                        expected: length,
+                        actual: bits.position - start,
+                    });
+                }
+            } else {
+                let count = bits.read(11)?;
+                for _ in 0..count {
+                    packets.push(Packet::decode(bits)?);
+                }
+            }
+
+            operator.check_operands(packets.len())?;
+
+            Ok(Packet {
+                version,
+                payload: Payload::Operator { operator, packets },
+            })
+        }
+
+        /// Writes this packet and its sub-packets as an indented tree, starting at `depth`.
+        fn fmt_tree(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+            write!(f, "{:indent$}v{} ", "", self.version, indent = depth * 2)?;
+            match &self.payload {
+                Payload::Literal(value) => writeln!(f, "literal {}", value),
+                Payload::Operator { operator, packets } => {
+                    writeln!(f, "{}", operator)?;
+                    for packet in packets {
+                        packet.fmt_tree(f, depth + 1)?;
+                    }
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    impl fmt::Display for Packet {
+        /// Formats the packet as a tree, one packet per line, with sub-packets indented
+        /// under their operator.
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.fmt_tree(f, 0)
+        }
+    }
+
+    /// A stream of bits being read from a transmission.
+    struct Bits {
+        bits: Vec<bool>,
+        position: usize,
+    }
+
+    impl Bits {
+        /// Converts the hexadecimal transmission `hex` into bits.
+        fn from_hex(hex: &str) -> Result<Bits, PacketError> {
+            let mut bits = Vec::with_capacity(hex.len() * 4);
+            for c in hex.chars() {
+                let digit = c.to_digit(16).ok_or(PacketError::InvalidHex(c))?;
+                for shift in (0..4).rev() {
+                    bits.push(digit >> shift & 1 == 1);
+                }
+            }
+            Ok(Bits { bits, position: 0 })
+        }
+
+        /// Reads the next `n` bits as an unsigned integer, most significant bit first.
+        fn read(&mut self, n: usize) -> Result<u64, PacketError> {
+            let end = self.position + n;
+            let bits = self
+                .bits
+                .get(self.position..end)
+                .ok_or_else(|| PacketError::Truncated {
+                    position: self.position,
+                    needed: end - self.bits.len(),
+                })?;
+            self.position = end;
+            Ok(bits.iter().fold(0, |acc, &bit| (acc << 1) | bit as u64))
+        }
+    }
+}
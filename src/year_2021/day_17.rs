@@ -0,0 +1,153 @@
+pub use probe::{Target, Trajectory};
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let target = Target::try_from(input)?;
+    match part {
+        1 => target
+            .highest_apex()
+            .map(|apex| apex.to_string())
+            .ok_or_else(|| io::Error::other("target cannot be hit")),
+        2 => Ok(target.velocities().len().to_string()),
+        _ => Err(crate::util::unknown_part(part)),
+    }
+}
+
+mod probe {
+    use std::io;
+    use std::ops::RangeInclusive;
+
+    /// The target area of a probe launch.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Target {
+        x: RangeInclusive<i32>,
+        y: RangeInclusive<i32>,
+    }
+
+    /// The path of a launched probe.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Trajectory {
+        /// Initial velocity `(x, y)` of the probe.
+        pub velocity: (i32, i32),
+        /// Positions of the probe after each step, up to and including the step on which it
+        /// entered the target area or passed beyond it.
+        pub positions: Vec<(i32, i32)>,
+        /// Whether the probe was inside the target area after any step.
+        pub hit: bool,
+    }
+
+    impl Trajectory {
+        /// Returns the highest `y` position of the flight, including the launch position.
+        ///
+        /// The recorded positions stop at the target, which a probe can hit on its way up,
+        /// so the apex comes from the initial velocity instead. A probe launched upwards at
+        /// `y` rises by `y`, `y - 1`, ... `1` before it starts to fall.
+        pub fn apex(&self) -> i32 {
+            let vy = self.velocity.1.max(0);
+            vy * (vy + 1) / 2
+        }
+    }
+
+    impl TryFrom<&str> for Target {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let invalid = || io::Error::other(format!("invalid target area: {}", value.trim()));
+
+            let ranges = value
+                .trim()
+                .strip_prefix("target area: ")
+                .ok_or_else(invalid)?;
+            let (x, y) = ranges.split_once(", ").ok_or_else(invalid)?;
+
+            let parse_range = |range: &str, axis: &str| {
+                let (min, max) = range
+                    .strip_prefix(axis)
+                    .and_then(|r| r.split_once(".."))
+                    .ok_or_else(invalid)?;
+                let min = min.parse::<i32>().map_err(|_| invalid())?;
+                let max = max.parse::<i32>().map_err(|_| invalid())?;
+                Ok::<_, io::Error>(min.min(max)..=min.max(max))
+            };
+
+            Ok(Target {
+                x: parse_range(x, "x=")?,
+                y: parse_range(y, "y=")?,
+            })
+        }
+    }
+
+    impl Target {
+        /// Returns the highest `y` position reached by any launch that hits the target, or
+        /// `None` if the target cannot be hit.
+        pub fn highest_apex(&self) -> Option<i32> {
+            self.velocities()
+                .into_iter()
+                .map(|velocity| self.trajectory(velocity).apex())
+                .max()
+        }
+
+        /// Returns every distinct initial velocity `(x, y)` that puts the probe inside the
+        /// target area after some step.
+        ///
+        /// The search is bounded by the target geometry. A probe's horizontal speed only
+        /// shrinks, so an `x` velocity beyond the far edge of the target overshoots on the
+        /// first step. Vertically, a probe launched upwards at `y` comes back down through
+        /// `0` at speed `y + 1`, so it cannot exceed the larger distance from `0` to the
+        /// target's top or bottom edge.
+        pub fn velocities(&self) -> Vec<(i32, i32)> {
+            let x_velocities = (*self.x.start()).min(0)..=(*self.x.end()).max(0);
+            let y_limit = self.y.start().abs().max(self.y.end().abs());
+            let y_velocities = (*self.y.start()).min(0)..=y_limit;
+
+            let mut velocities = vec![];
+            for x in x_velocities {
+                for y in y_velocities.clone() {
+                    if self.trajectory((x, y)).hit {
+                        velocities.push((x, y));
+                    }
+                }
+            }
+            velocities
+        }
+
+        /// Returns the trajectory of a probe launched from `(0, 0)` with initial
+        /// `velocity`.
+        pub fn trajectory(&self, velocity: (i32, i32)) -> Trajectory {
+            let (mut x, mut y) = (0, 0);
+            let (mut vx, mut vy) = velocity;
+            let mut positions = vec![];
+
+            loop {
+                x += vx;
+                y += vy;
+                vx -= vx.signum();
+                vy -= 1;
+                positions.push((x, y));
+
+                if self.x.contains(&x) && self.y.contains(&y) {
+                    return Trajectory {
+                        velocity,
+                        positions,
+                        hit: true,
+                    };
+                }
+
+                let past_x = match vx.signum() {
+                    1 => x > *self.x.end(),
+                    -1 => x < *self.x.start(),
+                    _ => !self.x.contains(&x),
+                };
+                let past_y = vy < 0 && y < *self.y.start();
+                if past_x || past_y {
+                    return Trajectory {
+                        velocity,
+                        positions,
+                        hit: false,
+                    };
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,281 @@
+pub use snailfish::{Homework, Number, Reduction};
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let homework = Homework::try_from(input)?;
+    let magnitude = match part {
+        1 => homework.sum().map(|n| n.magnitude()),
+        2 => homework.largest_pair_magnitude(),
+        _ => return Err(crate::util::unknown_part(part)),
+    };
+    magnitude
+        .map(|m| m.to_string())
+        .ok_or_else(|| io::Error::other("not enough snailfish numbers"))
+}
+
+mod snailfish {
+    use std::fmt;
+    use std::io;
+    use std::iter::Peekable;
+    use std::ops::Add;
+    use std::str::CharIndices;
+
+    /// Pairs nested inside this many pairs explode.
+    const EXPLODE_DEPTH: usize = 4;
+
+    /// Regular numbers of at least this value split.
+    const SPLIT_THRESHOLD: u32 = 10;
+
+    /// A snailfish number.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum Number {
+        Regular(u32),
+        Pair(Box<Number>, Box<Number>),
+    }
+
+    /// A single action taken while reducing a snailfish number.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Reduction {
+        /// The leftmost pair nested inside four pairs exploded.
+        Explode,
+        /// The leftmost regular number of ten or more split.
+        Split,
+    }
+
+    impl fmt::Display for Reduction {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                Reduction::Explode => write!(f, "explode"),
+                Reduction::Split => write!(f, "split"),
+            }
+        }
+    }
+
+    impl TryFrom<&str> for Number {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let value = value.trim();
+            let mut chars = value.char_indices().peekable();
+            let number = Number::parse(&mut chars)?;
+
+            match chars.next() {
+                None => Ok(number),
+                Some((i, c)) => Err(io::Error::other(format!(
+                    "unexpected {:?} at position {} of {}",
+                    c, i, value
+                ))),
+            }
+        }
+    }
+
+    impl fmt::Display for Number {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                Number::Regular(n) => write!(f, "{}", n),
+                Number::Pair(left, right) => write!(f, "[{},{}]", left, right),
+            }
+        }
+    }
+
+    impl Add for Number {
+        type Output = Number;
+
+        /// Adds two snailfish numbers by pairing them, then reducing the result.
+        fn add(self, rhs: Number) -> Number {
+            let mut sum = Number::Pair(Box::new(self), Box::new(rhs));
+            sum.reduce();
+            sum
+        }
+    }
+
+    impl Number {
+        /// Returns the magnitude of the number.
+        pub fn magnitude(&self) -> u64 {
+            match self {
+                Number::Regular(n) => *n as u64,
+                Number::Pair(left, right) => 3 * left.magnitude() + 2 * right.magnitude(),
+            }
+        }
+
+        /// Fully reduces the number.
+        pub fn reduce(&mut self) {
+            while self.reduce_step().is_some() {}
+        }
+
+        /// Fully reduces the number, returning each action taken along with the number as it
+        /// was after that action.
+        pub fn reduce_with_trace(&mut self) -> Vec<(Reduction, Number)> {
+            let mut trace = vec![];
+            while let Some(reduction) = self.reduce_step() {
+                trace.push((reduction, self.clone()));
+            }
+            trace
+        }
+
+        /// Takes the next action to reduce the number, returning the action taken, or `None`
+        /// if the number is already reduced. Explosions always take priority over splits.
+        pub fn reduce_step(&mut self) -> Option<Reduction> {
+            if self.explode(0).is_some() {
+                Some(Reduction::Explode)
+            } else if self.split() {
+                Some(Reduction::Split)
+            } else {
+                None
+            }
+        }
+
+        /// Explodes the leftmost pair of regular numbers nested at least `EXPLODE_DEPTH`
+        /// pairs deep, where this number is nested `depth` pairs deep. Returns `None` if
+        /// nothing exploded, else the left and right values that still need to be added to
+        /// the nearest regular numbers outside this number.
+        fn explode(&mut self, depth: usize) -> Option<(Option<u32>, Option<u32>)> {
+            let (left, right) = match self {
+                Number::Regular(_) => return None,
+                Number::Pair(left, right) => (left, right),
+            };
+
+            if depth >= EXPLODE_DEPTH {
+                if let (Number::Regular(l), Number::Regular(r)) = (left.as_ref(), right.as_ref()) {
+                    let carry = (Some(*l), Some(*r));
+                    *self = Number::Regular(0);
+                    return Some(carry);
+                }
+            }
+
+            if let Some((carry_left, carry_right)) = left.explode(depth + 1) {
+                if let Some(value) = carry_right {
+                    right.add_leftmost(value);
+                }
+                return Some((carry_left, None));
+            }
+
+            if let Some((carry_left, carry_right)) = right.explode(depth + 1) {
+                if let Some(value) = carry_left {
+                    left.add_rightmost(value);
+                }
+                return Some((None, carry_right));
+            }
+
+            None
+        }
+
+        /// Splits the leftmost regular number of at least `SPLIT_THRESHOLD`, returning
+        /// whether anything split.
+        fn split(&mut self) -> bool {
+            match self {
+                Number::Regular(n) if *n >= SPLIT_THRESHOLD => {
+                    *self = Number::Pair(
+                        Box::new(Number::Regular(*n / 2)),
+                        Box::new(Number::Regular(*n - *n / 2)),
+                    );
+                    true
+                }
+                Number::Regular(_) => false,
+                Number::Pair(left, right) => left.split() || right.split(),
+            }
+        }
+
+        /// Adds `value` to the leftmost regular number.
+        fn add_leftmost(&mut self, value: u32) {
+            match self {
+                Number::Regular(n) => *n += value,
+                Number::Pair(left, _) => left.add_leftmost(value),
+            }
+        }
+
+        /// Adds `value` to the rightmost regular number.
+        fn add_rightmost(&mut self, value: u32) {
+            match self {
+                Number::Regular(n) => *n += value,
+                Number::Pair(_, right) => right.add_rightmost(value),
+            }
+        }
+
+        /// Parses a number from the front of `chars`.
+        fn parse(chars: &mut Peekable<CharIndices>) -> io::Result<Number> {
+            match chars.next() {
+                Some((_, '[')) => {
+                    let left = Number::parse(chars)?;
+                    Number::expect(chars, ',')?;
+                    let right = Number::parse(chars)?;
+                    Number::expect(chars, ']')?;
+                    Ok(Number::Pair(Box::new(left), Box::new(right)))
+                }
+                Some((i, c)) if c.is_ascii_digit() => {
+                    let mut n = Some(c.to_digit(10).unwrap());
+                    while let Some(&(_, c)) = chars.peek() {
+                        match c.to_digit(10) {
+                            Some(d) => n = n.and_then(|n| n.checked_mul(10)?.checked_add(d)),
+                            None => break,
+                        }
+                        chars.next();
+                    }
+                    n.map(Number::Regular).ok_or_else(|| {
+                        io::Error::other(format!("invalid regular number at position {}", i))
+                    })
+                }
+                Some((i, c)) => Err(io::Error::other(format!(
+                    "expected '[' or a digit at position {}, found {:?}",
+                    i, c
+                ))),
+                None => Err(io::Error::other("unexpected end of snailfish number")),
+            }
+        }
+
+        /// Consumes the character `expected` from the front of `chars`.
+        fn expect(chars: &mut Peekable<CharIndices>, expected: char) -> io::Result<()> {
+            match chars.next() {
+                Some((_, c)) if c == expected => Ok(()),
+                Some((i, c)) => Err(io::Error::other(format!(
+                    "expected {:?} at position {}, found {:?}",
+                    expected, i, c
+                ))),
+                None => Err(io::Error::other(format!(
+                    "expected {:?}, found end of snailfish number",
+                    expected
+                ))),
+            }
+        }
+    }
+
+    /// A list of snailfish numbers.
+    #[derive(Clone, Debug)]
+    pub struct Homework(Vec<Number>);
+
+    impl TryFrom<&str> for Homework {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let numbers = value
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .map(Number::try_from)
+                .collect::<io::Result<Vec<_>>>()?;
+            Ok(Homework(numbers))
+        }
+    }
+
+    impl Homework {
+        /// Returns the sum of all numbers in order, or `None` if there are no numbers.
+        pub fn sum(&self) -> Option<Number> {
+            self.0.iter().cloned().reduce(|sum, n| sum + n)
+        }
+
+        /// Returns the largest magnitude of the sum of any two different numbers, in either
+        /// order, or `None` if there are fewer than two numbers.
+        pub fn largest_pair_magnitude(&self) -> Option<u64> {
+            let mut largest = None;
+            for (i, a) in self.0.iter().enumerate() {
+                for (j, b) in self.0.iter().enumerate() {
+                    if i != j {
+                        let magnitude = (a.clone() + b.clone()).magnitude();
+                        largest = largest.max(Some(magnitude));
+                    }
+                }
+            }
+            largest
+        }
+    }
+}
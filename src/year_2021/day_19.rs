@@ -0,0 +1,276 @@
+pub use scanner::{Alignment, Rotation, Scanners};
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let alignment = Scanners::try_from(input)?.align()?;
+    match part {
+        1 => Ok(alignment.beacon_count().to_string()),
+        2 => Ok(alignment.largest_scanner_distance().to_string()),
+        _ => Err(crate::util::unknown_part(part)),
+    }
+}
+
+mod scanner {
+    use std::cmp::Ordering;
+    use std::collections::{HashMap, HashSet, VecDeque};
+    use std::io;
+
+    /// Number of beacons two scanners must both detect for their alignment to be trusted.
+    const MIN_SHARED_BEACONS: usize = 12;
+
+    /// Number of pairwise beacon distances two scanners sharing `MIN_SHARED_BEACONS`
+    /// beacons must have in common.
+    const MIN_SHARED_DISTANCES: usize = MIN_SHARED_BEACONS * (MIN_SHARED_BEACONS - 1) / 2;
+
+    /// A position in three dimensions.
+    type Point = [i32; 3];
+
+    /// One of the 24 rotations that keep the axes aligned with the grid. Axis `i` of a
+    /// rotated point is axis `axes[i]` of the original point, multiplied by `signs[i]`.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Rotation {
+        pub axes: [usize; 3],
+        pub signs: [i32; 3],
+    }
+
+    impl Rotation {
+        /// Returns all 24 rotations, starting with the identity.
+        pub fn all() -> Vec<Rotation> {
+            let permutations = [
+                ([0, 1, 2], 1),
+                ([1, 2, 0], 1),
+                ([2, 0, 1], 1),
+                ([0, 2, 1], -1),
+                ([1, 0, 2], -1),
+                ([2, 1, 0], -1),
+            ];
+
+            let mut rotations = Vec::with_capacity(24);
+            for (axes, parity) in permutations {
+                for signs in [[1, 1, 1], [1, -1, -1], [-1, 1, -1], [-1, -1, 1]] {
+                    // Odd permutations flip handedness, which one negated axis undoes
+                    let signs = if parity == 1 {
+                        signs
+                    } else {
+                        [-signs[0], -signs[1], -signs[2]]
+                    };
+                    rotations.push(Rotation { axes, signs });
+                }
+            }
+            rotations
+        }
+
+        /// Rotates `point`.
+        pub fn apply(&self, point: Point) -> Point {
+            [
+                self.signs[0] * point[self.axes[0]],
+                self.signs[1] * point[self.axes[1]],
+                self.signs[2] * point[self.axes[2]],
+            ]
+        }
+    }
+
+    /// The beacons detected by one scanner, relative to the scanner's own position and
+    /// orientation.
+    #[derive(Clone, Debug)]
+    struct Scanner {
+        beacons: Vec<Point>,
+        /// Squared distances between every pair of beacons, sorted. These do not depend on
+        /// the scanner's position or orientation, so they cheaply rule out scanners that
+        /// cannot overlap before trying every rotation.
+        fingerprint: Vec<i64>,
+    }
+
+    impl Scanner {
+        /// Constructs a `Scanner` that detected `beacons`.
+        fn new(beacons: Vec<Point>) -> Scanner {
+            let mut fingerprint = vec![];
+            for (i, a) in beacons.iter().enumerate() {
+                for b in &beacons[i + 1..] {
+                    fingerprint.push((0..3).map(|k| ((a[k] - b[k]) as i64).pow(2)).sum());
+                }
+            }
+            fingerprint.sort_unstable();
+            Scanner {
+                beacons,
+                fingerprint,
+            }
+        }
+
+        /// Returns the number of pairwise beacon distances shared with `other`.
+        fn shared_distances(&self, other: &Scanner) -> usize {
+            let (mut i, mut j, mut shared) = (0, 0, 0);
+            while i < self.fingerprint.len() && j < other.fingerprint.len() {
+                match self.fingerprint[i].cmp(&other.fingerprint[j]) {
+                    Ordering::Less => i += 1,
+                    Ordering::Greater => j += 1,
+                    Ordering::Equal => {
+                        shared += 1;
+                        i += 1;
+                        j += 1;
+                    }
+                }
+            }
+            shared
+        }
+    }
+
+    /// The reports of every scanner.
+    #[derive(Clone, Debug)]
+    pub struct Scanners(Vec<Scanner>);
+
+    /// The scanners aligned to the position and orientation of the first scanner.
+    #[derive(Clone, Debug)]
+    pub struct Alignment {
+        /// Position of each scanner.
+        pub positions: Vec<Point>,
+        /// Rotation from each scanner's orientation to the first scanner's orientation.
+        pub rotations: Vec<Rotation>,
+        /// Every beacon detected by any scanner.
+        pub beacons: HashSet<Point>,
+    }
+
+    impl TryFrom<&str> for Scanners {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let mut scanners = vec![];
+            let mut beacons: Option<Vec<Point>> = None;
+
+            for line in value.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                if line.starts_with("---") {
+                    if let Some(beacons) = beacons.replace(vec![]) {
+                        scanners.push(Scanner::new(beacons));
+                    }
+                    continue;
+                }
+
+                let coordinates = line
+                    .split(',')
+                    .map(|s| s.parse::<i32>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .ok()
+                    .filter(|c| c.len() == 3)
+                    .ok_or_else(|| io::Error::other(format!("invalid beacon: {}", line)))?;
+
+                beacons
+                    .as_mut()
+                    .ok_or_else(|| io::Error::other("beacon listed before any scanner"))?
+                    .push([coordinates[0], coordinates[1], coordinates[2]]);
+            }
+
+            if let Some(beacons) = beacons {
+                scanners.push(Scanner::new(beacons));
+            }
+
+            Ok(Scanners(scanners))
+        }
+    }
+
+    impl Scanners {
+        /// Aligns every scanner to the first scanner, by finding a rotation and translation
+        /// under which it detects at least 12 of the same beacons as an aligned scanner.
+        /// Returns an error if some scanner cannot be aligned.
+        pub fn align(&self) -> io::Result<Alignment> {
+            let count = self.0.len();
+            let rotations = Rotation::all();
+
+            let mut positions = vec![None; count];
+            let mut orientations = vec![None; count];
+            // Beacons of each aligned scanner, relative to the first scanner
+            let mut aligned_beacons = vec![vec![]; count];
+            let mut queue = VecDeque::new();
+
+            if let Some(first) = self.0.first() {
+                positions[0] = Some([0, 0, 0]);
+                orientations[0] = Some(rotations[0]);
+                aligned_beacons[0] = first.beacons.clone();
+                queue.push_back(0);
+            }
+
+            while let Some(i) = queue.pop_front() {
+                for j in 0..count {
+                    if positions[j].is_some()
+                        || self.0[i].shared_distances(&self.0[j]) < MIN_SHARED_DISTANCES
+                    {
+                        continue;
+                    }
+
+                    if let Some((rotation, position)) =
+                        Scanners::find_transform(&aligned_beacons[i], &self.0[j], &rotations)
+                    {
+                        positions[j] = Some(position);
+                        orientations[j] = Some(rotation);
+                        aligned_beacons[j] = self.0[j]
+                            .beacons
+                            .iter()
+                            .map(|&b| add(rotation.apply(b), position))
+                            .collect();
+                        queue.push_back(j);
+                    }
+                }
+            }
+
+            match positions.iter().position(Option::is_none) {
+                Some(j) => Err(io::Error::other(format!("could not align scanner {}", j))),
+                None => Ok(Alignment {
+                    positions: positions.into_iter().flatten().collect(),
+                    rotations: orientations.into_iter().flatten().collect(),
+                    beacons: aligned_beacons.into_iter().flatten().collect(),
+                }),
+            }
+        }
+
+        /// Finds the rotation and position of `scanner` under which at least
+        /// `MIN_SHARED_BEACONS` of its beacons match the aligned `beacons`.
+        fn find_transform(
+            beacons: &[Point],
+            scanner: &Scanner,
+            rotations: &[Rotation],
+        ) -> Option<(Rotation, Point)> {
+            for &rotation in rotations {
+                let rotated: Vec<Point> =
+                    scanner.beacons.iter().map(|&b| rotation.apply(b)).collect();
+
+                // Each pair of beacons votes for the scanner position that would make them
+                // the same beacon
+                let mut votes: HashMap<Point, usize> = HashMap::new();
+                for a in beacons {
+                    for b in &rotated {
+                        let position = [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+                        let count = votes.entry(position).or_insert(0);
+                        *count += 1;
+                        if *count >= MIN_SHARED_BEACONS {
+                            return Some((rotation, position));
+                        }
+                    }
+                }
+            }
+            None
+        }
+    }
+
+    impl Alignment {
+        /// Returns the number of distinct beacons.
+        pub fn beacon_count(&self) -> usize {
+            self.beacons.len()
+        }
+
+        /// Returns the largest Manhattan distance between any two scanners.
+        pub fn largest_scanner_distance(&self) -> i32 {
+            let mut largest = 0;
+            for a in &self.positions {
+                for b in &self.positions {
+                    largest = largest.max((0..3).map(|k| (a[k] - b[k]).abs()).sum());
+                }
+            }
+            largest
+        }
+    }
+
+    /// Returns the sum of points `a` and `b`.
+    fn add(a: Point, b: Point) -> Point {
+        [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+    }
+}
@@ -0,0 +1,659 @@
+pub use direction::{
+    Directions, NavigationError, PlanCost, Position, ScriptError, Surface, Trajectory,
+};
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let directions = Directions::try_from(input)?;
+    let position = match part {
+        1 => directions.position(false),
+        2 => directions.position(true),
+        _ => return Err(crate::util::unknown_part(part)),
+    }
+    .map_err(io::Error::other)?;
+    position
+        .distance
+        .checked_mul(position.depth)
+        .map(|answer| answer.to_string())
+        .ok_or_else(|| {
+            io::Error::other(format!(
+                "{} * {} does not fit in 64 bits",
+                position.distance, position.depth
+            ))
+        })
+}
+
+mod direction {
+    use std::collections::HashMap;
+    use std::error::Error;
+    use std::fmt;
+    use std::io::{self, Write};
+    use std::iter;
+
+    /// Largest number of directions a script may compile to, once its `repeat` blocks are
+    /// expanded.
+    const MAX_DIRECTIONS: usize = 1 << 20;
+
+    /// Command for the submarine.
+    #[derive(Clone, Copy)]
+    enum Command {
+        /// Moves forward.
+        Forward,
+        /// Increases depth/aim.
+        Down,
+        /// Decreases depth/aim.
+        Up,
+        /// Moves backward, undoing a move forward.
+        Back,
+        /// Rises straight to the surface and levels out.
+        Surface,
+    }
+
+    /// Direction for the submarine.
+    #[derive(Clone, Copy)]
+    struct Direction {
+        /// Command for the direction.
+        command: Command,
+        /// Distance for the command.
+        distance: u32,
+    }
+
+    /// An error compiling a submarine script.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct ScriptError {
+        /// Line of the script the error is on, counting from `1`.
+        pub line: usize,
+        pub message: String,
+    }
+
+    impl fmt::Display for ScriptError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "line {}: {}", self.line, self.message)
+        }
+    }
+
+    impl Error for ScriptError {}
+
+    /// A word or symbol of a submarine script.
+    struct Token<'a> {
+        text: &'a str,
+        /// Line the token is on, counting from `1`.
+        line: usize,
+    }
+
+    /// Compiles a submarine script into directions, as described in `Directions::compile`.
+    struct Compiler<'a> {
+        tokens: iter::Peekable<std::vec::IntoIter<Token<'a>>>,
+        constants: HashMap<&'a str, u32>,
+        /// Line of the last token read, for errors at the end of the script.
+        line: usize,
+    }
+
+    impl<'a> Compiler<'a> {
+        /// Constructs a `Compiler` for `script`.
+        fn new(script: &'a str) -> Compiler<'a> {
+            let mut tokens = vec![];
+            for (i, line) in script.lines().enumerate() {
+                let code = line.split('#').next().unwrap_or("");
+                for word in code.split_whitespace() {
+                    // Braces and `=` are tokens of their own, even without surrounding spaces
+                    let mut start = 0;
+                    for (j, c) in word.char_indices() {
+                        if matches!(c, '{' | '}' | '=') {
+                            if start < j {
+                                tokens.push(Token {
+                                    text: &word[start..j],
+                                    line: i + 1,
+                                });
+                            }
+                            tokens.push(Token {
+                                text: &word[j..j + 1],
+                                line: i + 1,
+                            });
+                            start = j + 1;
+                        }
+                    }
+                    if start < word.len() {
+                        tokens.push(Token {
+                            text: &word[start..],
+                            line: i + 1,
+                        });
+                    }
+                }
+            }
+
+            Compiler {
+                tokens: tokens.into_iter().peekable(),
+                constants: HashMap::new(),
+                line: 1,
+            }
+        }
+
+        /// Compiles statements up to the end of the script, or up to the closing brace of
+        /// the `repeat` block opened on line `block` if it is not `None`.
+        fn compile(&mut self, block: Option<usize>) -> Result<Vec<Direction>, ScriptError> {
+            let mut directions = vec![];
+            loop {
+                let token = match self.tokens.next() {
+                    Some(token) => token,
+                    None => match block {
+                        Some(line) => return Err(error(line, "repeat block is never closed")),
+                        None => return Ok(directions),
+                    },
+                };
+                self.line = token.line;
+
+                let command = match token.text {
+                    "}" if block.is_some() => return Ok(directions),
+                    "}" => return Err(error(token.line, "unexpected '}'")),
+                    "let" => {
+                        let name = self.next("constant name")?;
+                        if !is_name(name.text) {
+                            return Err(error(
+                                name.line,
+                                &format!("invalid constant name: {}", name.text),
+                            ));
+                        }
+                        self.expect("=")?;
+                        let value = self.value()?;
+                        self.constants.insert(name.text, value);
+                        continue;
+                    }
+                    "repeat" => {
+                        let count = self.value()?;
+                        self.expect("{")?;
+                        let body = self.compile(Some(token.line))?;
+                        // Check the expanded length first, so that a huge count fails
+                        // instead of exhausting memory
+                        let len = match body.len().checked_mul(count as usize) {
+                            Some(len) if directions.len() + len <= MAX_DIRECTIONS => len,
+                            _ => {
+                                return Err(error(
+                                    token.line,
+                                    &format!(
+                                        "repeat expands to more than {} commands",
+                                        MAX_DIRECTIONS
+                                    ),
+                                ))
+                            }
+                        };
+                        directions.extend(body.iter().cycle().take(len));
+                        continue;
+                    }
+                    "surface" => {
+                        directions.push(Direction {
+                            command: Command::Surface,
+                            distance: 0,
+                        });
+                        continue;
+                    }
+                    "forward" => Command::Forward,
+                    "down" => Command::Down,
+                    "up" => Command::Up,
+                    "back" => Command::Back,
+                    other => return Err(error(token.line, &format!("unknown command: {}", other))),
+                };
+                let distance = self.value()?;
+                directions.push(Direction { command, distance });
+            }
+        }
+
+        /// Returns the next token, or an error saying `expected` is missing.
+        fn next(&mut self, expected: &str) -> Result<Token<'a>, ScriptError> {
+            let token = self
+                .tokens
+                .next()
+                .ok_or_else(|| error(self.line, &format!("expected {}", expected)))?;
+            self.line = token.line;
+            Ok(token)
+        }
+
+        /// Consumes the token `expected`.
+        fn expect(&mut self, expected: &str) -> Result<(), ScriptError> {
+            let token = self.next(&format!("'{}'", expected))?;
+            if token.text != expected {
+                return Err(error(
+                    token.line,
+                    &format!("expected '{}', found {}", expected, token.text),
+                ));
+            }
+            Ok(())
+        }
+
+        /// Reads a value, either a number or the name of a constant.
+        fn value(&mut self) -> Result<u32, ScriptError> {
+            let token = self.next("a value")?;
+            if let Ok(value) = token.text.parse::<u32>() {
+                return Ok(value);
+            }
+            if !is_name(token.text) {
+                return Err(error(token.line, &format!("invalid value: {}", token.text)));
+            }
+            self.constants
+                .get(token.text)
+                .copied()
+                .ok_or_else(|| error(token.line, &format!("undefined constant: {}", token.text)))
+        }
+    }
+
+    /// Returns a `ScriptError` on `line`.
+    fn error(line: usize, message: &str) -> ScriptError {
+        ScriptError {
+            line,
+            message: message.to_owned(),
+        }
+    }
+
+    /// Returns whether `text` can name a constant.
+    fn is_name(text: &str) -> bool {
+        const KEYWORDS: [&str; 7] = ["let", "repeat", "forward", "down", "up", "back", "surface"];
+        let mut chars = text.chars();
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !KEYWORDS.contains(&text)
+    }
+
+    /// A set of directions for the submarine.
+    pub struct Directions(Vec<Direction>);
+
+    impl fmt::Display for Directions {
+        /// Formats the directions as a script, one command per line.
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            for direction in &self.0 {
+                let command = match direction.command {
+                    Command::Forward => "forward",
+                    Command::Down => "down",
+                    Command::Up => "up",
+                    Command::Back => "back",
+                    Command::Surface => {
+                        writeln!(f, "surface")?;
+                        continue;
+                    }
+                };
+                writeln!(f, "{} {}", command, direction.distance)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// What a planned set of directions should be shortest in.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum PlanCost {
+        /// The number of commands.
+        Commands,
+        /// The sum of the distances of every command.
+        Distance,
+    }
+
+    /// A change made by a planned command: a move forward (or backward, if negative), or a
+    /// change of aim (or of depth, without aim) downwards (or upwards, if negative).
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Step {
+        Move(i64),
+        Aim(i64),
+    }
+
+    /// Represents the position of the submarine. Depth and aim grow downwards, so a negative
+    /// depth is above the surface.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct Position {
+        /// Horizontal distance of the submarine.
+        pub distance: i64,
+        /// Depth of the submarine.
+        pub depth: i64,
+        /// Aim of the submarine.
+        pub aim: i64,
+    }
+
+    impl Position {
+        /// Returns the position of the submarine after following `direction`, or `None` if
+        /// a coordinate does not fit in 64 bits.
+        fn moved(mut self, direction: &Direction, use_aim: bool) -> Option<Position> {
+            let x = direction.distance as i64;
+            match direction.command {
+                Command::Forward => {
+                    self.distance = self.distance.checked_add(x)?;
+                    if use_aim {
+                        self.depth = self.depth.checked_add(self.aim.checked_mul(x)?)?;
+                    }
+                }
+                Command::Down if use_aim => self.aim = self.aim.checked_add(x)?,
+                Command::Down => self.depth = self.depth.checked_add(x)?,
+                Command::Up if use_aim => self.aim = self.aim.checked_sub(x)?,
+                Command::Up => self.depth = self.depth.checked_sub(x)?,
+                Command::Back => {
+                    self.distance = self.distance.checked_sub(x)?;
+                    if use_aim {
+                        self.depth = self.depth.checked_sub(self.aim.checked_mul(x)?)?;
+                    }
+                }
+                Command::Surface => {
+                    self.depth = 0;
+                    self.aim = 0;
+                }
+            }
+            Some(self)
+        }
+    }
+
+    /// What to do when a command would take the submarine above the surface.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum Surface {
+        /// Let the depth go negative.
+        #[default]
+        Allow,
+        /// Stop the submarine at the surface, at depth `0`.
+        Clamp,
+        /// Fail with `NavigationError::AboveSurface`.
+        Reject,
+    }
+
+    /// An error following a set of directions.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum NavigationError {
+        /// The command at `index` would take the submarine to `depth`, above the surface.
+        AboveSurface { index: usize, depth: i64 },
+        /// The command at `index` takes a coordinate of the submarine beyond 64 bits.
+        Overflow { index: usize },
+    }
+
+    impl fmt::Display for NavigationError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                NavigationError::AboveSurface { index, depth } => write!(
+                    f,
+                    "command {} takes the submarine above the surface, to depth {}",
+                    index, depth
+                ),
+                NavigationError::Overflow { index } => write!(
+                    f,
+                    "command {} takes the submarine beyond 64-bit coordinates",
+                    index
+                ),
+            }
+        }
+    }
+
+    impl Error for NavigationError {}
+
+    impl fmt::Display for Position {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "(distance: {}, depth: {})", self.distance, self.depth)
+        }
+    }
+
+    impl TryFrom<&str> for Directions {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            Directions::compile(value).map_err(io::Error::other)
+        }
+    }
+
+    impl Directions {
+        /// Compiles a submarine script, a plain list of directions being the simplest
+        /// script.
+        ///
+        /// A script is a list of statements separated by whitespace:
+        ///
+        /// - `forward X`, `down X`, `up X` and `back X` move the submarine by `X`;
+        /// - `surface` takes the submarine straight to the surface;
+        /// - `let NAME = X` names the value `X`, so that `NAME` can be used in place of it;
+        /// - `repeat N { ... }` runs the statements between the braces `N` times.
+        ///
+        /// Values are either non-negative integers or names. Everything from a `#` to the
+        /// end of the line is a comment. A script may expand to at most 2^20 commands.
+        pub fn compile(script: &str) -> Result<Directions, ScriptError> {
+            Compiler::new(script).compile(None).map(Directions)
+        }
+
+        /// Calculates the position of the submarine based on the set of directions.
+        /// The `use_aim` flag determines whether to factor in the aim as part of the guidance system.
+        pub fn position(&self, use_aim: bool) -> Result<Position, NavigationError> {
+            self.navigate(use_aim, Surface::Allow)
+        }
+
+        /// Returns the position of the submarine after each command, following the same
+        /// rules as `position`.
+        pub fn trajectory(&self, use_aim: bool) -> Result<Trajectory, NavigationError> {
+            let mut position = Position::default();
+            let positions = self
+                .0
+                .iter()
+                .enumerate()
+                .map(|(index, direction)| {
+                    position = position
+                        .moved(direction, use_aim)
+                        .ok_or(NavigationError::Overflow { index })?;
+                    Ok(position)
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(Trajectory { positions })
+        }
+
+        /// Plans the shortest set of directions, by `cost`, that takes the submarine from the
+        /// origin to `distance` and `depth` under the rules of `position(use_aim)`. Returns
+        /// an error if a command would be longer than a direction can hold, or if replaying
+        /// the plan does not reach the target.
+        pub fn plan(
+            distance: i64,
+            depth: i64,
+            use_aim: bool,
+            cost: PlanCost,
+        ) -> io::Result<Directions> {
+            let steps = match (use_aim, cost) {
+                // Every command changes one coordinate, so one command per coordinate is
+                // shortest in both commands and distance
+                (false, _) => vec![Step::Move(distance), Step::Aim(depth)],
+                (true, _) if depth == 0 => vec![Step::Move(distance)],
+                (true, PlanCost::Commands) => Directions::plan_fewest_commands(distance, depth),
+                (true, PlanCost::Distance) => Directions::plan_least_distance(distance, depth),
+            };
+
+            // Merge adjacent steps of the same kind, dropping any that cancel out
+            let mut merged: Vec<Step> = vec![];
+            for step in steps {
+                match (merged.last_mut(), step) {
+                    (Some(Step::Move(last)), Step::Move(x))
+                    | (Some(Step::Aim(last)), Step::Aim(x)) => {
+                        *last += x;
+                        if *last == 0 {
+                            merged.pop();
+                        }
+                    }
+                    (_, Step::Move(0) | Step::Aim(0)) => {}
+                    _ => merged.push(step),
+                }
+            }
+
+            let directions = merged
+                .into_iter()
+                .map(|step| {
+                    let command = match step {
+                        Step::Move(x) if x > 0 => Command::Forward,
+                        Step::Move(_) => Command::Back,
+                        Step::Aim(x) if x > 0 => Command::Down,
+                        Step::Aim(_) => Command::Up,
+                    };
+                    let amount = match step {
+                        Step::Move(x) | Step::Aim(x) => x.unsigned_abs(),
+                    };
+                    let distance = u32::try_from(amount).map_err(|_| {
+                        io::Error::other(format!("planned command is too long: {}", amount))
+                    })?;
+                    Ok(Direction { command, distance })
+                })
+                .collect::<io::Result<Vec<_>>>()?;
+            let directions = Directions(directions);
+
+            let reached = directions.position(use_aim).map_err(io::Error::other)?;
+            if (reached.distance, reached.depth) != (distance, depth) {
+                return Err(io::Error::other(format!(
+                    "plan reaches {} instead of (distance: {}, depth: {})",
+                    reached, distance, depth
+                )));
+            }
+            Ok(directions)
+        }
+
+        /// Plans the fewest aim-based steps reaching a non-zero `depth`.
+        ///
+        /// One command only moves or aims, which cannot change depth. Two commands, aiming
+        /// then moving, reach depths that are a multiple of the distance. Anything else takes
+        /// three: a move at aim `0`, an aim, then a move of some divisor of the depth, chosen
+        /// to keep the total distance small.
+        fn plan_fewest_commands(distance: i64, depth: i64) -> Vec<Step> {
+            if distance != 0 && depth % distance == 0 {
+                return vec![Step::Aim(depth / distance), Step::Move(distance)];
+            }
+
+            let cost = |m: i64| (distance - m).abs() + (depth / m).abs() + m.abs();
+            let magnitude = depth.abs();
+            let m = (1..)
+                .take_while(|&d| d * d <= magnitude)
+                .filter(|&d| magnitude % d == 0)
+                .flat_map(|d| [d, -d, magnitude / d, -magnitude / d])
+                .min_by_key(|&m| cost(m))
+                .unwrap_or(depth);
+            vec![
+                Step::Move(distance - m),
+                Step::Aim(depth / m),
+                Step::Move(m),
+            ]
+        }
+
+        /// Plans aim-based steps of least total distance reaching a non-zero `depth`.
+        ///
+        /// Aiming at `a` and moving `m` reaches depths up to `a * m`, and raising the aim
+        /// partway reaches every depth in between, so a total move of `m` needs a total aim
+        /// of `ceil(|depth| / m)`. Moving further than `distance` can pay off for deep,
+        /// short targets, the excess being undone by a move in the opposite direction at aim
+        /// `0`. This picks the total move `m` with the least total distance.
+        fn plan_least_distance(distance: i64, depth: i64) -> Vec<Step> {
+            let (target, magnitude) = (distance.abs(), depth.abs());
+            let cost = |m: i64| (target - m).abs() + (magnitude + m - 1) / m + m;
+
+            let mut best = target.max(1);
+            let mut m = best;
+            // Past the target, every extra unit of move costs two units of distance
+            while 2 * m - target <= cost(best) {
+                if cost(m) < cost(best) {
+                    best = m;
+                }
+                m += 1;
+            }
+
+            let sign = if distance < 0 { -1 } else { 1 };
+            let aim_sign = depth.signum() * sign;
+            let (q, r) = (magnitude / best, magnitude % best);
+            vec![
+                Step::Move(distance - sign * best),
+                Step::Aim(aim_sign * q),
+                Step::Move(sign * (best - r)),
+                Step::Aim(aim_sign * (r > 0) as i64),
+                Step::Move(sign * r),
+            ]
+        }
+
+        /// Calculates the position of the submarine like `position`, handling commands that
+        /// would take it above the surface according to `surface`. Returns an error if a
+        /// command takes a coordinate beyond 64 bits.
+        pub fn navigate(
+            &self,
+            use_aim: bool,
+            surface: Surface,
+        ) -> Result<Position, NavigationError> {
+            let mut position = Position::default();
+            for (index, direction) in self.0.iter().enumerate() {
+                position = position
+                    .moved(direction, use_aim)
+                    .ok_or(NavigationError::Overflow { index })?;
+                if position.depth < 0 {
+                    match surface {
+                        Surface::Allow => {}
+                        Surface::Clamp => position.depth = 0,
+                        Surface::Reject => {
+                            return Err(NavigationError::AboveSurface {
+                                index,
+                                depth: position.depth,
+                            })
+                        }
+                    }
+                }
+            }
+            Ok(position)
+        }
+    }
+
+    /// The positions of the submarine after each command of a set of directions.
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct Trajectory {
+        pub positions: Vec<Position>,
+    }
+
+    impl Trajectory {
+        /// Width of the SVG depth profile, in pixels.
+        const SVG_WIDTH: u32 = 800;
+
+        /// Height of the SVG depth profile, in pixels.
+        const SVG_HEIGHT: u32 = 400;
+
+        /// Writes the trajectory as CSV with a header row, one row per command, numbered
+        /// from `0`.
+        pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+            writeln!(writer, "command,distance,depth,aim")?;
+            for (index, position) in self.positions.iter().enumerate() {
+                writeln!(
+                    writer,
+                    "{},{},{},{}",
+                    index, position.distance, position.depth, position.aim
+                )?;
+            }
+            Ok(())
+        }
+
+        /// Writes the trajectory as an SVG depth profile: distance runs left to right and
+        /// depth top to bottom, starting from the origin, with a line marking the surface.
+        /// The view is stretched to fit the whole trajectory.
+        pub fn write_svg<W: Write>(&self, mut writer: W) -> io::Result<()> {
+            let origin = Position::default();
+            let points: Vec<&Position> = iter::once(&origin).chain(&self.positions).collect();
+
+            let min_x = points.iter().map(|p| p.distance).min().unwrap_or(0);
+            let max_x = points.iter().map(|p| p.distance).max().unwrap_or(0);
+            let min_y = points.iter().map(|p| p.depth).min().unwrap_or(0);
+            let max_y = points.iter().map(|p| p.depth).max().unwrap_or(0);
+            let (width, height) = ((max_x - min_x).max(1), (max_y - min_y).max(1));
+
+            writeln!(
+                writer,
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}" preserveAspectRatio="none">"#,
+                Self::SVG_WIDTH,
+                Self::SVG_HEIGHT,
+                min_x,
+                min_y,
+                width,
+                height
+            )?;
+            writeln!(
+                writer,
+                r##"  <line x1="{}" y1="0" x2="{}" y2="0" stroke="#4a90d9" stroke-dasharray="4" vector-effect="non-scaling-stroke"/>"##,
+                min_x,
+                max_x.max(min_x + 1)
+            )?;
+            let polyline: Vec<String> = points
+                .iter()
+                .map(|p| format!("{},{}", p.distance, p.depth))
+                .collect();
+            writeln!(
+                writer,
+                r#"  <polyline points="{}" fill="none" stroke="black" vector-effect="non-scaling-stroke"/>"#,
+                polyline.join(" ")
+            )?;
+            writeln!(writer, "</svg>")
+        }
+    }
+}
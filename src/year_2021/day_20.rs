@@ -0,0 +1,191 @@
+use std::io;
+pub use trench::{Image, TrenchMap};
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let mut trench_map = TrenchMap::try_from(input)?;
+    match part {
+        1 => trench_map.enhance_times(2),
+        2 => trench_map.enhance_times(50),
+        _ => return Err(crate::util::unknown_part(part)),
+    }
+    trench_map
+        .image
+        .lit()
+        .map(|lit| lit.to_string())
+        .ok_or_else(|| io::Error::other("infinitely many pixels are lit"))
+}
+
+mod trench {
+    use std::fmt;
+    use std::io;
+
+    /// Number of entries in an image enhancement algorithm.
+    const ALGORITHM_SIZE: usize = 512;
+
+    /// An image of infinite size. Only a finite window of pixels is stored; every pixel
+    /// outside the window has the same state, the background.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Image {
+        /// Pixels inside the window in row-major order, `true` if lit.
+        pixels: Vec<bool>,
+        width: usize,
+        height: usize,
+        /// Whether every pixel outside the window is lit.
+        background: bool,
+    }
+
+    /// An image together with the algorithm used to enhance it.
+    #[derive(Clone, Debug)]
+    pub struct TrenchMap {
+        /// For each 9-bit index built from a pixel's neighbourhood, whether the pixel is lit
+        /// in the enhanced image.
+        algorithm: [bool; ALGORITHM_SIZE],
+        pub image: Image,
+    }
+
+    impl TryFrom<&str> for TrenchMap {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let mut lines = value.lines().map(str::trim).filter(|l| !l.is_empty());
+
+            let algorithm_line = lines
+                .next()
+                .ok_or_else(|| io::Error::other("missing image enhancement algorithm"))?;
+            let algorithm_pixels = parse_pixels(algorithm_line)?;
+            let algorithm: [bool; ALGORITHM_SIZE] =
+                algorithm_pixels.try_into().map_err(|p: Vec<bool>| {
+                    io::Error::other(format!(
+                        "image enhancement algorithm has {} entries, want {}",
+                        p.len(),
+                        ALGORITHM_SIZE
+                    ))
+                })?;
+
+            let mut pixels = vec![];
+            let mut width = 0;
+            let mut height = 0;
+            for line in lines {
+                let row = parse_pixels(line)?;
+                if height == 0 {
+                    width = row.len();
+                } else if row.len() != width {
+                    return Err(io::Error::other(format!(
+                        "image row {} does not have width {}",
+                        height, width
+                    )));
+                }
+                pixels.extend(row);
+                height += 1;
+            }
+
+            Ok(TrenchMap {
+                algorithm,
+                image: Image {
+                    pixels,
+                    width,
+                    height,
+                    background: false,
+                },
+            })
+        }
+    }
+
+    impl TrenchMap {
+        /// Enhances the image once. The window grows by one pixel on every side, as those
+        /// are the only pixels outside it whose neighbourhood overlaps the window.
+        pub fn enhance(&mut self) {
+            let image = &self.image;
+            let (width, height) = (image.width + 2, image.height + 2);
+            let mut pixels = Vec::with_capacity(width * height);
+
+            for row in 0..height as isize {
+                for column in 0..width as isize {
+                    let mut index = 0;
+                    for dr in -1..=1 {
+                        for dc in -1..=1 {
+                            // Window coordinates of the neighbour, shifted by the new border
+                            let lit = image.get(row + dr - 1, column + dc - 1);
+                            index = (index << 1) | lit as usize;
+                        }
+                    }
+                    pixels.push(self.algorithm[index]);
+                }
+            }
+
+            // Every background pixel's neighbourhood is all background, so when index 0 of
+            // the algorithm is lit, the background flips on every step
+            let background = if image.background {
+                self.algorithm[ALGORITHM_SIZE - 1]
+            } else {
+                self.algorithm[0]
+            };
+
+            self.image = Image {
+                pixels,
+                width,
+                height,
+                background,
+            };
+        }
+
+        /// Enhances the image `times` times.
+        pub fn enhance_times(&mut self, times: usize) {
+            for _ in 0..times {
+                self.enhance();
+            }
+        }
+    }
+
+    impl Image {
+        /// Returns the number of lit pixels, or `None` if the background is lit, in which
+        /// case infinitely many pixels are lit.
+        pub fn lit(&self) -> Option<usize> {
+            if self.background {
+                None
+            } else {
+                Some(self.pixels.iter().filter(|&&p| p).count())
+            }
+        }
+
+        /// Returns whether the background, every pixel outside the window, is lit.
+        pub fn background(&self) -> bool {
+            self.background
+        }
+
+        /// Returns whether the pixel at `row` and `column` of the window is lit. Pixels
+        /// outside the window are background pixels.
+        fn get(&self, row: isize, column: isize) -> bool {
+            if row < 0 || column < 0 || row as usize >= self.height || column as usize >= self.width
+            {
+                return self.background;
+            }
+            self.pixels[row as usize * self.width + column as usize]
+        }
+    }
+
+    impl fmt::Display for Image {
+        /// Renders the window of the image, `#` for lit pixels and `.` for dark pixels.
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            for row in self.pixels.chunks(self.width.max(1)) {
+                for &pixel in row {
+                    write!(f, "{}", if pixel { "#" } else { "." })?;
+                }
+                writeln!(f)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Parses a line of `#` (lit) and `.` (dark) pixels.
+    fn parse_pixels(line: &str) -> io::Result<Vec<bool>> {
+        line.chars()
+            .map(|c| match c {
+                '#' => Ok(true),
+                '.' => Ok(false),
+                _ => Err(io::Error::other(format!("invalid pixel: {}", c))),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,195 @@
+pub use dice::{DeterministicDie, Game, Outcome};
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let game = Game::try_from(input)?;
+    match part {
+        1 => {
+            let outcome = game.play_deterministic(&mut DeterministicDie::new(100));
+            Ok((outcome.losing_score() * outcome.rolls).to_string())
+        }
+        2 => Ok(game.quantum_wins(21, 3).iter().max().unwrap().to_string()),
+        _ => Err(crate::util::unknown_part(part)),
+    }
+}
+
+mod dice {
+    use std::collections::HashMap;
+    use std::io;
+
+    /// Number of spaces on the circular game board.
+    const BOARD_SIZE: u32 = 10;
+
+    /// Number of times a player rolls the die on each turn.
+    const ROLLS_PER_TURN: u32 = 3;
+
+    /// Score a player needs to win with the deterministic die.
+    const DETERMINISTIC_TARGET: u32 = 1000;
+
+    /// A die that rolls `1`, `2`, ... up to its number of faces, then starts over at `1`.
+    #[derive(Clone, Debug)]
+    pub struct DeterministicDie {
+        faces: u32,
+        next: u32,
+    }
+
+    impl DeterministicDie {
+        /// Constructs a die with `faces` faces.
+        pub fn new(faces: u32) -> DeterministicDie {
+            DeterministicDie { faces, next: 1 }
+        }
+
+        /// Rolls the die.
+        pub fn roll(&mut self) -> u32 {
+            let value = self.next;
+            self.next = value % self.faces + 1;
+            value
+        }
+    }
+
+    /// The result of a game with the deterministic die.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Outcome {
+        /// Final score of each player.
+        pub scores: [u32; 2],
+        /// Number of times the die was rolled.
+        pub rolls: u32,
+    }
+
+    impl Outcome {
+        /// Returns the score of the player who lost.
+        pub fn losing_score(&self) -> u32 {
+            self.scores[0].min(self.scores[1])
+        }
+    }
+
+    /// The starting positions of both players, each from `1` to `BOARD_SIZE`.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Game {
+        pub positions: [u32; 2],
+    }
+
+    impl TryFrom<&str> for Game {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let mut positions = [0; 2];
+            let mut lines = value.lines().map(str::trim).filter(|l| !l.is_empty());
+
+            for (player, position) in positions.iter_mut().enumerate() {
+                let line = lines.next().ok_or_else(|| {
+                    io::Error::other(format!(
+                        "missing starting position of player {}",
+                        player + 1
+                    ))
+                })?;
+                *position = line
+                    .strip_prefix(&format!("Player {} starting position: ", player + 1))
+                    .and_then(|p| p.parse::<u32>().ok())
+                    .filter(|p| (1..=BOARD_SIZE).contains(p))
+                    .ok_or_else(|| {
+                        io::Error::other(format!("invalid starting position: {}", line))
+                    })?;
+            }
+
+            Ok(Game { positions })
+        }
+    }
+
+    impl Game {
+        /// Plays the game with `die` until a player reaches a score of 1000.
+        pub fn play_deterministic(&self, die: &mut DeterministicDie) -> Outcome {
+            let mut positions = self.positions;
+            let mut scores = [0; 2];
+            let mut rolls = 0;
+
+            for player in [0, 1].into_iter().cycle() {
+                let moves: u32 = (0..ROLLS_PER_TURN).map(|_| die.roll()).sum();
+                rolls += ROLLS_PER_TURN;
+                positions[player] = advance(positions[player], moves);
+                scores[player] += positions[player];
+                if scores[player] >= DETERMINISTIC_TARGET {
+                    break;
+                }
+            }
+
+            Outcome { scores, rolls }
+        }
+
+        /// Returns the number of universes in which each player wins, when every roll of a
+        /// die with `faces` faces splits the universe into one copy per face, and the first
+        /// player to reach `target` wins.
+        ///
+        /// Every turn raises the mover's score, so the wins from each state only depend on
+        /// states with a higher combined score. The table of wins is filled bottom-up from
+        /// the highest combined score down to the starting state.
+        pub fn quantum_wins(&self, target: u32, faces: u32) -> [u128; 2] {
+            let sums = roll_sums(faces);
+            // A target of `0` is reached on the first turn, just like a target of `1`
+            let target = target.max(1) as usize;
+            let board = BOARD_SIZE as usize;
+
+            // Wins of the player about to move and of the other player, by the position
+            // (from `0`) and score of each
+            let index = |current: (usize, usize), other: (usize, usize)| {
+                ((current.0 * target + current.1) * board + other.0) * target + other.1
+            };
+            let mut wins = vec![[0u128; 2]; board * board * target * target];
+
+            for total in (0..=2 * (target - 1)).rev() {
+                for score in total.saturating_sub(target - 1)..=total.min(target - 1) {
+                    let other_score = total - score;
+                    for position in 0..board {
+                        for other_position in 0..board {
+                            let mut state_wins = [0, 0];
+                            for &(sum, universes) in &sums {
+                                let next = advance(position as u32 + 1, sum) as usize;
+                                let next_score = score + next;
+                                if next_score >= target {
+                                    state_wins[0] += universes;
+                                } else {
+                                    // The other player moves next, so their wins come first
+                                    let [other_wins, current_wins] = wins[index(
+                                        (other_position, other_score),
+                                        (next - 1, next_score),
+                                    )];
+                                    state_wins[0] += universes * current_wins;
+                                    state_wins[1] += universes * other_wins;
+                                }
+                            }
+                            wins[index((position, score), (other_position, other_score))] =
+                                state_wins;
+                        }
+                    }
+                }
+            }
+
+            let start = |player: usize| (self.positions[player] as usize - 1, 0);
+            wins[index(start(0), start(1))]
+        }
+    }
+
+    /// Returns each possible sum of one turn's rolls of a die with `faces` faces, with the
+    /// number of ways to roll it.
+    fn roll_sums(faces: u32) -> Vec<(u32, u128)> {
+        let mut counts: HashMap<u32, u128> = HashMap::from([(0, 1)]);
+        for _ in 0..ROLLS_PER_TURN {
+            let mut next = HashMap::new();
+            for (sum, count) in counts {
+                for face in 1..=faces {
+                    *next.entry(sum + face).or_insert(0) += count;
+                }
+            }
+            counts = next;
+        }
+        let mut sums: Vec<_> = counts.into_iter().collect();
+        sums.sort_unstable();
+        sums
+    }
+
+    /// Returns the position reached by moving `moves` spaces forward from `position`.
+    fn advance(position: u32, moves: u32) -> u32 {
+        (position - 1 + moves) % BOARD_SIZE + 1
+    }
+}
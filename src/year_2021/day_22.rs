@@ -0,0 +1,165 @@
+pub use reactor::{Cuboid, RebootStep, RebootSteps};
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let steps = RebootSteps::try_from(input)?;
+    match part {
+        1 => Ok(steps.lit_within(&Cuboid::INITIALIZATION).to_string()),
+        2 => Ok(steps.lit().to_string()),
+        _ => Err(crate::util::unknown_part(part)),
+    }
+}
+
+mod reactor {
+    use std::io;
+
+    /// A cuboid of cubes, from `min` to `max` inclusive along each axis.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Cuboid {
+        pub min: [i64; 3],
+        pub max: [i64; 3],
+    }
+
+    impl Cuboid {
+        /// The region considered by the initialization procedure.
+        pub const INITIALIZATION: Cuboid = Cuboid {
+            min: [-50; 3],
+            max: [50; 3],
+        };
+
+        /// Returns the number of cubes in the cuboid. Side lengths are widened before they
+        /// are multiplied, so that no cuboid's volume can overflow.
+        pub fn volume(&self) -> i128 {
+            (0..3)
+                .map(|k| self.max[k] as i128 - self.min[k] as i128 + 1)
+                .product()
+        }
+
+        /// Returns the cuboid of cubes in both this cuboid and `other`, or `None` if they do
+        /// not overlap.
+        pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+            let mut intersection = Cuboid {
+                min: [0; 3],
+                max: [0; 3],
+            };
+            for k in 0..3 {
+                intersection.min[k] = self.min[k].max(other.min[k]);
+                intersection.max[k] = self.max[k].min(other.max[k]);
+                if intersection.min[k] > intersection.max[k] {
+                    return None;
+                }
+            }
+            Some(intersection)
+        }
+    }
+
+    /// A single step of the reboot process, turning every cube in a cuboid on or off.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct RebootStep {
+        pub on: bool,
+        pub cuboid: Cuboid,
+    }
+
+    impl TryFrom<&str> for RebootStep {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let invalid = || io::Error::other(format!("invalid reboot step: {}", value));
+
+            let (state, ranges) = value.split_once(' ').ok_or_else(invalid)?;
+            let on = match state {
+                "on" => true,
+                "off" => false,
+                _ => return Err(invalid()),
+            };
+
+            let mut cuboid = Cuboid {
+                min: [0; 3],
+                max: [0; 3],
+            };
+            let mut ranges = ranges.split(',');
+            for (k, axis) in ["x=", "y=", "z="].iter().enumerate() {
+                let (min, max) = ranges
+                    .next()
+                    .and_then(|r| r.strip_prefix(axis))
+                    .and_then(|r| r.split_once(".."))
+                    .ok_or_else(invalid)?;
+                // 32-bit coordinates keep every volume well within `i128`
+                let min = min.parse::<i32>().map_err(|_| invalid())? as i64;
+                let max = max.parse::<i32>().map_err(|_| invalid())? as i64;
+                cuboid.min[k] = min.min(max);
+                cuboid.max[k] = min.max(max);
+            }
+            if ranges.next().is_some() {
+                return Err(invalid());
+            }
+
+            Ok(RebootStep { on, cuboid })
+        }
+    }
+
+    /// The steps of the reboot process, in order.
+    #[derive(Clone, Debug)]
+    pub struct RebootSteps(Vec<RebootStep>);
+
+    impl TryFrom<&str> for RebootSteps {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let steps = value
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(RebootStep::try_from)
+                .collect::<io::Result<Vec<_>>>()?;
+            Ok(RebootSteps(steps))
+        }
+    }
+
+    impl RebootSteps {
+        /// Returns the number of cubes that are on after every step.
+        pub fn lit(&self) -> i128 {
+            Self::count(self.0.iter().copied())
+        }
+
+        /// Returns the number of cubes inside `region` that are on after every step.
+        pub fn lit_within(&self, region: &Cuboid) -> i128 {
+            Self::count(self.0.iter().filter_map(|step| {
+                step.cuboid.intersection(region).map(|cuboid| RebootStep {
+                    on: step.on,
+                    cuboid,
+                })
+            }))
+        }
+
+        /// Returns the number of cubes that are on after `steps`.
+        ///
+        /// Rather than tracking individual cubes, this keeps a list of cuboids with signed
+        /// volumes whose sum is the number of cubes on. Each step cancels out its overlap
+        /// with every cuboid already in the list by adding the intersection with the
+        /// opposite sign, then, if it turns cubes on, adds its own cuboid.
+        fn count(steps: impl Iterator<Item = RebootStep>) -> i128 {
+            let mut signed: Vec<(Cuboid, i8)> = vec![];
+            for step in steps {
+                let overlaps: Vec<_> = signed
+                    .iter()
+                    .filter_map(|(cuboid, sign)| {
+                        step.cuboid
+                            .intersection(cuboid)
+                            .map(|overlap| (overlap, -sign))
+                    })
+                    .collect();
+                signed.extend(overlaps);
+                if step.on {
+                    signed.push((step.cuboid, 1));
+                }
+            }
+
+            signed
+                .iter()
+                .map(|(cuboid, sign)| *sign as i128 * cuboid.volume())
+                .sum()
+        }
+    }
+}
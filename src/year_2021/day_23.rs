@@ -0,0 +1,499 @@
+pub use amphipod::{Amphipod, Burrow, Location, Move, Solution};
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let burrow = Burrow::try_from(input)?;
+    let burrow = match part {
+        1 => burrow,
+        2 => burrow.unfold()?,
+        _ => return Err(crate::util::unknown_part(part)),
+    };
+    burrow
+        .organize()
+        .map(|solution| solution.energy.to_string())
+        .ok_or_else(|| io::Error::other("burrow cannot be organized"))
+}
+
+mod amphipod {
+    use std::cmp::Reverse;
+    use std::collections::{BinaryHeap, HashMap};
+    use std::fmt;
+    use std::io;
+
+    /// Number of spaces in the hallway.
+    const HALLWAY_LEN: usize = 11;
+
+    /// Number of side rooms.
+    const ROOMS: usize = 4;
+
+    /// Largest number of amphipods a side room can hold.
+    const MAX_DEPTH: usize = 4;
+
+    /// Number of bits encoding the contents of one space in a `Burrow`'s state.
+    const SPACE_BITS: usize = 3;
+
+    /// Rows inserted into each side room when the burrow diagram is unfolded.
+    const UNFOLDED_ROWS: [&str; 2] = ["DCBA", "DBAC"];
+
+    /// A type of amphipod.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum Amphipod {
+        Amber,
+        Bronze,
+        Copper,
+        Desert,
+    }
+
+    impl Amphipod {
+        /// Returns the amphipod drawn as `c` in a burrow diagram, or `None` if `c` is not an
+        /// amphipod.
+        fn from_char(c: char) -> Option<Amphipod> {
+            match c {
+                'A' => Some(Amphipod::Amber),
+                'B' => Some(Amphipod::Bronze),
+                'C' => Some(Amphipod::Copper),
+                'D' => Some(Amphipod::Desert),
+                _ => None,
+            }
+        }
+
+        /// Returns the amphipod encoded as `code` in a `Burrow`'s state, or `None` if `code`
+        /// encodes an empty space.
+        fn from_code(code: u128) -> Option<Amphipod> {
+            match code {
+                1 => Some(Amphipod::Amber),
+                2 => Some(Amphipod::Bronze),
+                3 => Some(Amphipod::Copper),
+                4 => Some(Amphipod::Desert),
+                _ => None,
+            }
+        }
+
+        /// Returns the code of the amphipod in a `Burrow`'s state.
+        fn code(self) -> u128 {
+            self.room() as u128 + 1
+        }
+
+        /// Returns the side room the amphipod belongs in.
+        fn room(self) -> usize {
+            match self {
+                Amphipod::Amber => 0,
+                Amphipod::Bronze => 1,
+                Amphipod::Copper => 2,
+                Amphipod::Desert => 3,
+            }
+        }
+
+        /// Returns the energy required for the amphipod to move one step.
+        pub fn energy(self) -> u32 {
+            match self {
+                Amphipod::Amber => 1,
+                Amphipod::Bronze => 10,
+                Amphipod::Copper => 100,
+                Amphipod::Desert => 1000,
+            }
+        }
+    }
+
+    impl fmt::Display for Amphipod {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let c = match self {
+                Amphipod::Amber => 'A',
+                Amphipod::Bronze => 'B',
+                Amphipod::Copper => 'C',
+                Amphipod::Desert => 'D',
+            };
+            write!(f, "{}", c)
+        }
+    }
+
+    /// A space in the burrow.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum Location {
+        /// A space in the hallway, counted from the left.
+        Hallway(usize),
+        /// A space in a side room, counted from the left, `depth` spaces below the top.
+        Room { room: usize, depth: usize },
+    }
+
+    impl fmt::Display for Location {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                Location::Hallway(position) => write!(f, "hallway {}", position),
+                Location::Room { room, depth } => write!(f, "room {} depth {}", room, depth),
+            }
+        }
+    }
+
+    /// A single move of an amphipod.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Move {
+        pub amphipod: Amphipod,
+        pub from: Location,
+        pub to: Location,
+        /// Energy spent by the move.
+        pub energy: u32,
+    }
+
+    impl fmt::Display for Move {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                f,
+                "{} from {} to {} ({} energy)",
+                self.amphipod, self.from, self.to, self.energy
+            )
+        }
+    }
+
+    /// The moves organizing a burrow with the least total energy.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Solution {
+        pub energy: u32,
+        pub moves: Vec<Move>,
+    }
+
+    /// A burrow of amphipods, a hallway with `ROOMS` side rooms below it.
+    ///
+    /// The contents of every space are packed into a single integer, `SPACE_BITS` per
+    /// space, so states are cheap to copy, hash and compare during the search.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct Burrow {
+        /// Number of amphipods each side room holds.
+        depth: usize,
+        /// The hallway spaces followed by the spaces of each side room from top to bottom.
+        state: u128,
+    }
+
+    impl TryFrom<&str> for Burrow {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let lines: Vec<Vec<char>> = value
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .map(|l| l.chars().collect())
+                .collect();
+
+            let hallway = lines
+                .get(1)
+                .and_then(|l| l.get(1..=HALLWAY_LEN))
+                .ok_or_else(|| io::Error::other("missing hallway"))?;
+            let rows: Vec<&[char]> = lines[2..]
+                .iter()
+                .map(|l| l.as_slice())
+                .take_while(|l| l.get(3).is_some_and(|&c| c == '.' || c.is_alphabetic()))
+                .collect();
+            if rows.is_empty() || rows.len() > MAX_DEPTH {
+                return Err(io::Error::other(format!(
+                    "side rooms must hold 1 to {} amphipods, found {}",
+                    MAX_DEPTH,
+                    rows.len()
+                )));
+            }
+
+            let mut burrow = Burrow {
+                depth: rows.len(),
+                state: 0,
+            };
+            let parse = |c: char| match c {
+                '.' => Ok(None),
+                _ => Amphipod::from_char(c)
+                    .map(Some)
+                    .ok_or_else(|| io::Error::other(format!("invalid space: {:?}", c))),
+            };
+
+            for (position, &c) in hallway.iter().enumerate() {
+                burrow.set(Location::Hallway(position), parse(c)?);
+            }
+            for (depth, row) in rows.iter().enumerate() {
+                for room in 0..ROOMS {
+                    let c = row.get(3 + 2 * room).copied().unwrap_or(' ');
+                    burrow.set(Location::Room { room, depth }, parse(c)?);
+                }
+            }
+
+            let mut counts = [0; ROOMS];
+            for location in burrow.locations() {
+                if let Some(amphipod) = burrow.get(location) {
+                    counts[amphipod.room()] += 1;
+                }
+            }
+            if counts.iter().any(|&count| count != burrow.depth) {
+                return Err(io::Error::other(format!(
+                    "burrow must contain {} amphipods of each type",
+                    burrow.depth
+                )));
+            }
+
+            Ok(burrow)
+        }
+    }
+
+    impl Burrow {
+        /// Returns the number of amphipods each side room holds.
+        pub fn depth(&self) -> usize {
+            self.depth
+        }
+
+        /// Returns the amphipod at `location`, or `None` if the space is empty.
+        pub fn get(&self, location: Location) -> Option<Amphipod> {
+            let shift = self.index(location) * SPACE_BITS;
+            Amphipod::from_code(self.state >> shift & ((1 << SPACE_BITS) - 1))
+        }
+
+        /// Puts `amphipod` at `location`, or empties the space if `amphipod` is `None`.
+        fn set(&mut self, location: Location, amphipod: Option<Amphipod>) {
+            let shift = self.index(location) * SPACE_BITS;
+            self.state &= !(((1 << SPACE_BITS) - 1) << shift);
+            self.state |= amphipod.map_or(0, Amphipod::code) << shift;
+        }
+
+        /// Returns the position of `location` among the spaces of the burrow.
+        fn index(&self, location: Location) -> usize {
+            match location {
+                Location::Hallway(position) => position,
+                Location::Room { room, depth } => HALLWAY_LEN + room * self.depth + depth,
+            }
+        }
+
+        /// Returns every space of the burrow.
+        fn locations(&self) -> impl Iterator<Item = Location> + '_ {
+            (0..HALLWAY_LEN).map(Location::Hallway).chain(
+                (0..ROOMS)
+                    .flat_map(move |room| (0..self.depth).map(move |depth| (room, depth)))
+                    .map(|(room, depth)| Location::Room { room, depth }),
+            )
+        }
+
+        /// Returns the burrow with the two folded rows of the full diagram inserted between
+        /// the top and bottom rows of each side room.
+        pub fn unfold(&self) -> io::Result<Burrow> {
+            if self.depth != 2 {
+                return Err(io::Error::other(format!(
+                    "only burrows with side rooms of depth 2 can be unfolded, found {}",
+                    self.depth
+                )));
+            }
+
+            let mut unfolded = Burrow {
+                depth: self.depth + UNFOLDED_ROWS.len(),
+                state: 0,
+            };
+            for position in 0..HALLWAY_LEN {
+                let location = Location::Hallway(position);
+                unfolded.set(location, self.get(location));
+            }
+            for room in 0..ROOMS {
+                let top = self.get(Location::Room { room, depth: 0 });
+                let bottom = self.get(Location::Room { room, depth: 1 });
+                unfolded.set(Location::Room { room, depth: 0 }, top);
+                for (i, row) in UNFOLDED_ROWS.iter().enumerate() {
+                    let amphipod = row.chars().nth(room).and_then(Amphipod::from_char);
+                    unfolded.set(Location::Room { room, depth: i + 1 }, amphipod);
+                }
+                unfolded.set(
+                    Location::Room {
+                        room,
+                        depth: unfolded.depth - 1,
+                    },
+                    bottom,
+                );
+            }
+            Ok(unfolded)
+        }
+
+        /// Returns whether every amphipod is in its own side room.
+        pub fn is_organized(&self) -> bool {
+            (0..ROOMS).all(|room| {
+                (0..self.depth).all(|depth| {
+                    self.get(Location::Room { room, depth })
+                        .is_some_and(|a| a.room() == room)
+                })
+            })
+        }
+
+        /// Returns every legal move from this burrow.
+        ///
+        /// An amphipod leaves its side room only if the room holds an amphipod that does not
+        /// belong there, and it stops in the hallway anywhere but directly outside a room.
+        /// From the hallway, it only moves into its own side room, as deep as possible, once
+        /// that room holds no other type of amphipod. Moving directly between rooms costs
+        /// the same as stopping in the hallway in between, so it is not a separate move.
+        pub fn moves(&self) -> Vec<Move> {
+            let mut moves = vec![];
+
+            for room in 0..ROOMS {
+                let Some(depth) = (0..self.depth)
+                    .find(|&depth| self.get(Location::Room { room, depth }).is_some())
+                else {
+                    continue;
+                };
+                if self.room_accepts(room) {
+                    continue;
+                }
+
+                let from = Location::Room { room, depth };
+                let amphipod = self.get(from).unwrap();
+                let door = door(room);
+                for position in (0..HALLWAY_LEN).filter(|&p| !is_door(p)) {
+                    if self.hallway_clear(door, position) {
+                        let steps = depth + 1 + door.abs_diff(position);
+                        moves.push(Move {
+                            amphipod,
+                            from,
+                            to: Location::Hallway(position),
+                            energy: steps as u32 * amphipod.energy(),
+                        });
+                    }
+                }
+            }
+
+            for position in 0..HALLWAY_LEN {
+                let from = Location::Hallway(position);
+                let Some(amphipod) = self.get(from) else {
+                    continue;
+                };
+                let room = amphipod.room();
+                let door = door(room);
+                if !self.room_accepts(room) || !self.hallway_clear(position, door) {
+                    continue;
+                }
+
+                let depth = (0..self.depth)
+                    .take_while(|&depth| self.get(Location::Room { room, depth }).is_none())
+                    .count()
+                    - 1;
+                let steps = position.abs_diff(door) + depth + 1;
+                moves.push(Move {
+                    amphipod,
+                    from,
+                    to: Location::Room { room, depth },
+                    energy: steps as u32 * amphipod.energy(),
+                });
+            }
+
+            moves
+        }
+
+        /// Returns whether `room` holds only amphipods that belong there.
+        fn room_accepts(&self, room: usize) -> bool {
+            (0..self.depth).all(|depth| {
+                self.get(Location::Room { room, depth })
+                    .is_none_or(|a| a.room() == room)
+            })
+        }
+
+        /// Returns whether every hallway space after `from` up to and including `to` is
+        /// empty.
+        fn hallway_clear(&self, from: usize, to: usize) -> bool {
+            (from.min(to)..=from.max(to))
+                .filter(|&p| p != from)
+                .all(|p| self.get(Location::Hallway(p)).is_none())
+        }
+
+        /// Makes `mv`, returning an error if it is not a legal move from this burrow.
+        pub fn apply(&mut self, mv: &Move) -> io::Result<()> {
+            if !self.moves().contains(mv) {
+                return Err(io::Error::other(format!("illegal move: {}", mv)));
+            }
+            self.perform(mv);
+            Ok(())
+        }
+
+        /// Makes `mv` without checking that it is legal.
+        fn perform(&mut self, mv: &Move) {
+            self.set(mv.from, None);
+            self.set(mv.to, Some(mv.amphipod));
+        }
+
+        /// Makes each of `moves` in order, returning the burrow after each move. Returns an
+        /// error if any move is not legal.
+        pub fn replay(&self, moves: &[Move]) -> io::Result<Vec<Burrow>> {
+            let mut burrow = *self;
+            let mut frames = Vec::with_capacity(moves.len());
+            for mv in moves {
+                burrow.apply(mv)?;
+                frames.push(burrow);
+            }
+            Ok(frames)
+        }
+
+        /// Returns the moves organizing the burrow with the least total energy, or `None` if
+        /// the burrow cannot be organized, using Dijkstra's algorithm over burrow states.
+        pub fn organize(&self) -> Option<Solution> {
+            let mut energies = HashMap::from([(self.state, 0)]);
+            let mut previous: HashMap<u128, (u128, Move)> = HashMap::new();
+            let mut queue = BinaryHeap::from([Reverse((0, self.state))]);
+
+            while let Some(Reverse((energy, state))) = queue.pop() {
+                if energies.get(&state).is_some_and(|&e| e < energy) {
+                    continue;
+                }
+
+                let burrow = Burrow {
+                    depth: self.depth,
+                    state,
+                };
+                if burrow.is_organized() {
+                    let mut moves = vec![];
+                    let mut state = state;
+                    while let Some(&(before, mv)) = previous.get(&state) {
+                        moves.push(mv);
+                        state = before;
+                    }
+                    moves.reverse();
+                    return Some(Solution { energy, moves });
+                }
+
+                for mv in burrow.moves() {
+                    let mut next = burrow;
+                    next.perform(&mv);
+                    let next_energy = energy + mv.energy;
+                    if energies.get(&next.state).is_none_or(|&e| next_energy < e) {
+                        energies.insert(next.state, next_energy);
+                        previous.insert(next.state, (state, mv));
+                        queue.push(Reverse((next_energy, next.state)));
+                    }
+                }
+            }
+
+            None
+        }
+    }
+
+    impl fmt::Display for Burrow {
+        /// Renders the burrow as a diagram in the same format as the puzzle input.
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let space = |location| {
+                self.get(location)
+                    .map_or('.'.to_string(), |a| a.to_string())
+            };
+
+            writeln!(f, "{}", "#".repeat(HALLWAY_LEN + 2))?;
+            write!(f, "#")?;
+            for position in 0..HALLWAY_LEN {
+                write!(f, "{}", space(Location::Hallway(position)))?;
+            }
+            writeln!(f, "#")?;
+            for depth in 0..self.depth {
+                write!(f, "{}", if depth == 0 { "###" } else { "  #" })?;
+                for room in 0..ROOMS {
+                    write!(f, "{}#", space(Location::Room { room, depth }))?;
+                }
+                writeln!(f, "{}", if depth == 0 { "##" } else { "" })?;
+            }
+            writeln!(f, "  {}", "#".repeat(2 * ROOMS + 1))
+        }
+    }
+
+    /// Returns the hallway position directly outside `room`.
+    fn door(room: usize) -> usize {
+        2 + 2 * room
+    }
+
+    /// Returns whether hallway `position` is directly outside a side room.
+    fn is_door(position: usize) -> bool {
+        (0..ROOMS).any(|room| door(room) == position)
+    }
+}
@@ -0,0 +1,388 @@
+pub use alu::{Alu, Constraint, Instruction, Monad, Operand, Program, Register};
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let program = Program::try_from(input)?;
+    let monad = Monad::try_from(&program)?;
+    let model = match part {
+        1 => monad.largest(),
+        2 => monad.smallest(),
+        _ => return Err(crate::util::unknown_part(part)),
+    }
+    .ok_or_else(|| io::Error::other("no model number is accepted"))?;
+
+    // The analysis relies on the usual shape of MONAD, so double check its answer
+    if !program.accepts(model)? {
+        return Err(io::Error::other(format!(
+            "MONAD rejects model number {}",
+            model
+        )));
+    }
+    Ok(model.to_string())
+}
+
+mod alu {
+    use std::fmt;
+    use std::io;
+
+    /// Number of digits in a model number.
+    const MODEL_DIGITS: usize = 14;
+
+    /// Number of instructions MONAD uses to process each digit.
+    const BLOCK_LEN: usize = 18;
+
+    /// One of the ALU's four variables.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Register {
+        W,
+        X,
+        Y,
+        Z,
+    }
+
+    impl Register {
+        /// Returns the position of the register in `Alu::registers`.
+        fn index(self) -> usize {
+            match self {
+                Register::W => 0,
+                Register::X => 1,
+                Register::Y => 2,
+                Register::Z => 3,
+            }
+        }
+    }
+
+    impl TryFrom<&str> for Register {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            match value {
+                "w" => Ok(Register::W),
+                "x" => Ok(Register::X),
+                "y" => Ok(Register::Y),
+                "z" => Ok(Register::Z),
+                _ => Err(io::Error::other(format!("invalid variable: {}", value))),
+            }
+        }
+    }
+
+    impl fmt::Display for Register {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let name = match self {
+                Register::W => "w",
+                Register::X => "x",
+                Register::Y => "y",
+                Register::Z => "z",
+            };
+            write!(f, "{}", name)
+        }
+    }
+
+    /// The second operand of an instruction, a variable or a number.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Operand {
+        Register(Register),
+        Number(i64),
+    }
+
+    impl TryFrom<&str> for Operand {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            match value.parse::<i64>() {
+                Ok(n) => Ok(Operand::Number(n)),
+                Err(_) => Register::try_from(value).map(Operand::Register),
+            }
+        }
+    }
+
+    impl fmt::Display for Operand {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                Operand::Register(register) => write!(f, "{}", register),
+                Operand::Number(n) => write!(f, "{}", n),
+            }
+        }
+    }
+
+    /// A single ALU instruction.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Instruction {
+        Inp(Register),
+        Add(Register, Operand),
+        Mul(Register, Operand),
+        Div(Register, Operand),
+        Mod(Register, Operand),
+        Eql(Register, Operand),
+    }
+
+    impl TryFrom<&str> for Instruction {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let invalid = || io::Error::other(format!("invalid instruction: {}", value));
+
+            let mut parts = value.split_whitespace();
+            let opcode = parts.next().ok_or_else(invalid)?;
+            let a = Register::try_from(parts.next().ok_or_else(invalid)?)?;
+            let instruction = if opcode == "inp" {
+                Instruction::Inp(a)
+            } else {
+                let b = Operand::try_from(parts.next().ok_or_else(invalid)?)?;
+                match opcode {
+                    "add" => Instruction::Add(a, b),
+                    "mul" => Instruction::Mul(a, b),
+                    "div" => Instruction::Div(a, b),
+                    "mod" => Instruction::Mod(a, b),
+                    "eql" => Instruction::Eql(a, b),
+                    _ => return Err(invalid()),
+                }
+            };
+
+            match parts.next() {
+                None => Ok(instruction),
+                Some(_) => Err(invalid()),
+            }
+        }
+    }
+
+    impl fmt::Display for Instruction {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                Instruction::Inp(a) => write!(f, "inp {}", a),
+                Instruction::Add(a, b) => write!(f, "add {} {}", a, b),
+                Instruction::Mul(a, b) => write!(f, "mul {} {}", a, b),
+                Instruction::Div(a, b) => write!(f, "div {} {}", a, b),
+                Instruction::Mod(a, b) => write!(f, "mod {} {}", a, b),
+                Instruction::Eql(a, b) => write!(f, "eql {} {}", a, b),
+            }
+        }
+    }
+
+    /// A list of ALU instructions.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Program(pub Vec<Instruction>);
+
+    impl TryFrom<&str> for Program {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let instructions = value
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(Instruction::try_from)
+                .collect::<io::Result<Vec<_>>>()?;
+            Ok(Program(instructions))
+        }
+    }
+
+    impl Program {
+        /// Returns whether MONAD, this program, accepts `model`, by running it with the
+        /// model number's digits as input and checking that `z` ends up `0`.
+        pub fn accepts(&self, model: u64) -> io::Result<bool> {
+            let digits = model
+                .to_string()
+                .chars()
+                .map(|c| c.to_digit(10).unwrap() as i64)
+                .collect::<Vec<_>>();
+            let mut alu = Alu::default();
+            alu.run(self, digits)?;
+            Ok(alu.get(Register::Z) == 0)
+        }
+    }
+
+    /// The arithmetic logic unit of the submarine.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct Alu {
+        registers: [i64; 4],
+    }
+
+    impl Alu {
+        /// Returns the value of `register`.
+        pub fn get(&self, register: Register) -> i64 {
+            self.registers[register.index()]
+        }
+
+        /// Runs `program`, reading each `inp` instruction's value from `input`. Returns an
+        /// error if the input runs out, on division by zero or an invalid modulo, which
+        /// would crash the ALU, or if a result does not fit in 64 bits.
+        pub fn run(
+            &mut self,
+            program: &Program,
+            input: impl IntoIterator<Item = i64>,
+        ) -> io::Result<()> {
+            let mut input = input.into_iter();
+            for (line, &instruction) in program.0.iter().enumerate() {
+                self.execute(instruction, &mut input).map_err(|e| {
+                    io::Error::other(format!("line {}: {}: {}", line + 1, instruction, e))
+                })?;
+            }
+            Ok(())
+        }
+
+        /// Executes a single instruction, reading from `input` if it is an `inp`
+        /// instruction.
+        pub fn execute(
+            &mut self,
+            instruction: Instruction,
+            input: &mut impl Iterator<Item = i64>,
+        ) -> io::Result<()> {
+            let (a, value) = match instruction {
+                Instruction::Inp(a) => {
+                    let value = input
+                        .next()
+                        .ok_or_else(|| io::Error::other("input exhausted"))?;
+                    (a, value)
+                }
+                Instruction::Add(a, b) => {
+                    let (x, y) = (self.get(a), self.value(b));
+                    (a, x.checked_add(y).ok_or_else(|| overflow(x, '+', y))?)
+                }
+                Instruction::Mul(a, b) => {
+                    let (x, y) = (self.get(a), self.value(b));
+                    (a, x.checked_mul(y).ok_or_else(|| overflow(x, '*', y))?)
+                }
+                Instruction::Div(a, b) => {
+                    let (dividend, divisor) = (self.get(a), self.value(b));
+                    if divisor == 0 {
+                        return Err(io::Error::other("division by zero"));
+                    }
+                    let quotient = dividend
+                        .checked_div(divisor)
+                        .ok_or_else(|| overflow(dividend, '/', divisor))?;
+                    (a, quotient)
+                }
+                Instruction::Mod(a, b) => {
+                    let (dividend, divisor) = (self.get(a), self.value(b));
+                    if dividend < 0 || divisor <= 0 {
+                        return Err(io::Error::other(format!(
+                            "invalid modulo: {} % {}",
+                            dividend, divisor
+                        )));
+                    }
+                    (a, dividend % divisor)
+                }
+                Instruction::Eql(a, b) => (a, (self.get(a) == self.value(b)) as i64),
+            };
+            self.registers[a.index()] = value;
+            Ok(())
+        }
+
+        /// Returns the value of `operand`.
+        fn value(&self, operand: Operand) -> i64 {
+            match operand {
+                Operand::Register(register) => self.get(register),
+                Operand::Number(n) => n,
+            }
+        }
+    }
+
+    /// Returns the error for an operation `x op y` whose result does not fit in 64 bits.
+    fn overflow(x: i64, op: char, y: i64) -> io::Error {
+        io::Error::other(format!("overflow: {} {} {}", x, op, y))
+    }
+
+    /// A relation between two digits of every accepted model number:
+    /// `digits[pop] == digits[push] + offset`.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Constraint {
+        pub push: usize,
+        pub pop: usize,
+        pub offset: i64,
+    }
+
+    /// The constraints MONAD places on model numbers.
+    ///
+    /// MONAD processes each digit with the same block of instructions, differing only in
+    /// three numbers. `z` acts as a stack of base 26 numbers: blocks that divide `z` by `1`
+    /// push `digit + y_offset`, and blocks that divide `z` by `26` pop the top value and
+    /// push again unless `digit == top + x_offset`. `z` can only end up `0` if every pop
+    /// avoids pushing, which ties each popping digit to the digit that pushed.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Monad {
+        pub constraints: Vec<Constraint>,
+    }
+
+    impl TryFrom<&Program> for Monad {
+        type Error = io::Error;
+
+        fn try_from(program: &Program) -> Result<Self, Self::Error> {
+            if program.0.len() != MODEL_DIGITS * BLOCK_LEN {
+                return Err(io::Error::other(format!(
+                    "MONAD should have {} instructions, found {}",
+                    MODEL_DIGITS * BLOCK_LEN,
+                    program.0.len()
+                )));
+            }
+
+            let mut stack = vec![];
+            let mut constraints = vec![];
+            for (digit, block) in program.0.chunks(BLOCK_LEN).enumerate() {
+                let (pops, x_offset, y_offset) = match (block[0], block[4], block[5], block[15]) {
+                    (
+                        Instruction::Inp(Register::W),
+                        Instruction::Div(Register::Z, Operand::Number(divisor @ (1 | 26))),
+                        Instruction::Add(Register::X, Operand::Number(x_offset)),
+                        Instruction::Add(Register::Y, Operand::Number(y_offset)),
+                    ) => (divisor == 26, x_offset, y_offset),
+                    _ => {
+                        return Err(io::Error::other(format!(
+                            "block for digit {} does not have the shape of MONAD",
+                            digit + 1
+                        )))
+                    }
+                };
+
+                if pops {
+                    let (push, y_offset) = stack.pop().ok_or_else(|| {
+                        io::Error::other(format!("digit {} pops an empty stack", digit + 1))
+                    })?;
+                    constraints.push(Constraint {
+                        push,
+                        pop: digit,
+                        offset: y_offset + x_offset,
+                    });
+                } else {
+                    stack.push((digit, y_offset));
+                }
+            }
+
+            if !stack.is_empty() {
+                return Err(io::Error::other("MONAD pushes more digits than it pops"));
+            }
+
+            Ok(Monad { constraints })
+        }
+    }
+
+    impl Monad {
+        /// Returns the largest model number satisfying every constraint, or `None` if
+        /// some constraint cannot be satisfied by digits from `1` to `9`.
+        pub fn largest(&self) -> Option<u64> {
+            self.model(|offset| (9.min(9 - offset), 9.min(9 + offset)))
+        }
+
+        /// Returns the smallest model number satisfying every constraint, or `None` if
+        /// some constraint cannot be satisfied by digits from `1` to `9`.
+        pub fn smallest(&self) -> Option<u64> {
+            self.model(|offset| (1.max(1 - offset), 1.max(1 + offset)))
+        }
+
+        /// Builds a model number by choosing the pushing and popping digit of each
+        /// constraint with `choose`, given the constraint's offset.
+        fn model(&self, choose: impl Fn(i64) -> (i64, i64)) -> Option<u64> {
+            let mut digits = [0; MODEL_DIGITS];
+            for constraint in &self.constraints {
+                let (push, pop) = choose(constraint.offset);
+                if !(1..=9).contains(&push) || !(1..=9).contains(&pop) {
+                    return None;
+                }
+                digits[constraint.push] = push;
+                digits[constraint.pop] = pop;
+            }
+            Some(digits.iter().fold(0, |model, &d| model * 10 + d as u64))
+        }
+    }
+}
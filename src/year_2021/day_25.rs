@@ -0,0 +1,189 @@
+pub use herd::{SeaFloor, Space, Steps};
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let sea_floor = SeaFloor::try_from(input)?;
+    match part {
+        1 => sea_floor
+            .first_stationary_step()
+            .map(|step| step.to_string())
+            .ok_or_else(|| io::Error::other("sea cucumbers never stop moving")),
+        _ => Err(crate::util::unknown_part(part)),
+    }
+}
+
+mod herd {
+    use std::fmt;
+    use std::io;
+
+    /// The contents of a location on the sea floor.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Space {
+        Empty,
+        /// A sea cucumber of the east-facing herd.
+        East,
+        /// A sea cucumber of the south-facing herd.
+        South,
+    }
+
+    /// A grid of sea cucumbers. Sea cucumbers moving off the right edge reappear on the
+    /// left edge, and those moving off the bottom edge reappear on the top edge.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct SeaFloor {
+        /// Spaces in row-major order.
+        spaces: Vec<Space>,
+        width: usize,
+        height: usize,
+    }
+
+    impl TryFrom<&str> for SeaFloor {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let mut spaces = vec![];
+            let mut width = 0;
+            let mut height = 0;
+
+            for line in value.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                if height == 0 {
+                    width = line.len();
+                } else if line.len() != width {
+                    return Err(io::Error::other(format!(
+                        "row {} does not have width {}",
+                        height, width
+                    )));
+                }
+                for c in line.chars() {
+                    spaces.push(match c {
+                        '.' => Space::Empty,
+                        '>' => Space::East,
+                        'v' => Space::South,
+                        _ => return Err(io::Error::other(format!("invalid space: {:?}", c))),
+                    });
+                }
+                height += 1;
+            }
+
+            Ok(SeaFloor {
+                spaces,
+                width,
+                height,
+            })
+        }
+    }
+
+    impl SeaFloor {
+        /// Returns the contents of the space at `row` and `column`.
+        pub fn get(&self, row: usize, column: usize) -> Space {
+            self.spaces[row * self.width + column]
+        }
+
+        /// Moves both herds one step, returning the number of sea cucumbers that moved.
+        /// The east-facing herd moves first, then the south-facing herd; within a herd,
+        /// every sea cucumber decides whether to move before any of them moves.
+        pub fn step(&mut self) -> usize {
+            self.move_herd(Space::East, 0, 1) + self.move_herd(Space::South, 1, 0)
+        }
+
+        /// Moves every sea cucumber of `herd` by `dr` rows and `dc` columns whose
+        /// destination is empty, returning the number that moved.
+        fn move_herd(&mut self, herd: Space, dr: usize, dc: usize) -> usize {
+            let mut moves = vec![];
+            for row in 0..self.height {
+                for column in 0..self.width {
+                    let from = row * self.width + column;
+                    let to = (row + dr) % self.height * self.width + (column + dc) % self.width;
+                    if self.spaces[from] == herd && self.spaces[to] == Space::Empty {
+                        moves.push((from, to));
+                    }
+                }
+            }
+
+            for &(from, to) in &moves {
+                self.spaces[from] = Space::Empty;
+                self.spaces[to] = herd;
+            }
+            moves.len()
+        }
+
+        /// Returns an iterator over the sea floor after each step, ending with the first
+        /// step on which no sea cucumbers move. The iterator never ends if the herds keep
+        /// moving in a cycle.
+        pub fn steps(&self) -> Steps {
+            Steps {
+                sea_floor: self.clone(),
+                done: false,
+            }
+        }
+
+        /// Returns the number of the first step, counting from `1`, on which no sea
+        /// cucumbers move, or `None` if the herds keep moving in a cycle forever.
+        ///
+        /// Cycles are found with Brent's algorithm: the sea floor is compared with a saved
+        /// copy after each step, and the copy is replaced whenever the number of steps since
+        /// it was saved reaches the next power of two. Once the sea floor repeats a state
+        /// in which a sea cucumber moved, it repeats it forever.
+        pub fn first_stationary_step(&self) -> Option<usize> {
+            let mut sea_floor = self.clone();
+            let mut saved = self.clone();
+            let mut power = 1;
+            let mut since_saved = 0;
+            let mut step = 0;
+
+            loop {
+                step += 1;
+                if sea_floor.step() == 0 {
+                    return Some(step);
+                }
+                if sea_floor == saved {
+                    return None;
+                }
+                since_saved += 1;
+                if since_saved == power {
+                    saved = sea_floor.clone();
+                    power *= 2;
+                    since_saved = 0;
+                }
+            }
+        }
+    }
+
+    impl fmt::Display for SeaFloor {
+        /// Renders the sea floor in the same format as the puzzle input.
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            for row in self.spaces.chunks(self.width.max(1)) {
+                for space in row {
+                    let c = match space {
+                        Space::Empty => '.',
+                        Space::East => '>',
+                        Space::South => 'v',
+                    };
+                    write!(f, "{}", c)?;
+                }
+                writeln!(f)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// An iterator over the sea floor after each step, ending with the first step on which
+    /// no sea cucumbers move.
+    #[derive(Clone, Debug)]
+    pub struct Steps {
+        sea_floor: SeaFloor,
+        done: bool,
+    }
+
+    impl Iterator for Steps {
+        type Item = SeaFloor;
+
+        fn next(&mut self) -> Option<SeaFloor> {
+            if self.done {
+                return None;
+            }
+            self.done = self.sea_floor.step() == 0;
+            Some(self.sea_floor.clone())
+        }
+    }
+}
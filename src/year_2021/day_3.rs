@@ -0,0 +1,232 @@
+pub use diagnostic::{BinaryDiagnostic, DiagnosticError};
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let binary_diagnostic = BinaryDiagnostic::try_from(input).map_err(io::Error::other)?;
+    match part {
+        1 => Ok(binary_diagnostic.power_consumption().to_string()),
+        2 => Ok(binary_diagnostic.life_support_rating().to_string()),
+        _ => Err(crate::util::unknown_part(part)),
+    }
+}
+
+mod diagnostic {
+    use std::cmp::Ordering;
+    use std::error::Error;
+    use std::fmt;
+
+    /// The largest number of binary digits in a reading, so that the product of two
+    /// readings fits in a `u64`.
+    const MAX_WIDTH: usize = 32;
+
+    /// Number of readings packed into each word of a column.
+    const WORD_BITS: usize = u64::BITS as usize;
+
+    /// An error reading binary diagnostic data.
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum DiagnosticError {
+        /// The data has no readings.
+        Empty,
+        /// Line `line` has `width` binary digits, unlike the first reading's `expected`.
+        RaggedLine {
+            line: usize,
+            width: usize,
+            expected: usize,
+        },
+        /// Line `line` contains `found`, which is not a binary digit.
+        NonBinary { line: usize, found: char },
+        /// The readings have `width` binary digits, more than `MAX_WIDTH`.
+        TooWide { width: usize },
+    }
+
+    impl fmt::Display for DiagnosticError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                DiagnosticError::Empty => write!(f, "diagnostic data has no readings"),
+                DiagnosticError::RaggedLine {
+                    line,
+                    width,
+                    expected,
+                } => write!(
+                    f,
+                    "line {} has {} binary digits, expected {}",
+                    line, width, expected
+                ),
+                DiagnosticError::NonBinary { line, found } => {
+                    write!(f, "line {} contains non-binary digit {:?}", line, found)
+                }
+                DiagnosticError::TooWide { width } => write!(
+                    f,
+                    "readings have {} binary digits, at most {} are supported",
+                    width, MAX_WIDTH
+                ),
+            }
+        }
+    }
+
+    impl Error for DiagnosticError {}
+
+    /// The binary diagnostic info for the submarine.
+    ///
+    /// Readings are stored bit-packed by column: bit `j` of column `i` is the `i`th binary
+    /// digit, from the left, of reading `j`. Counting the ones among any set of readings at
+    /// a position is then a popcount over one column, 64 readings at a time.
+    pub struct BinaryDiagnostic {
+        columns: Vec<Vec<u64>>,
+        /// The number of readings.
+        len: usize,
+        /// The number of binary digits in each reading.
+        width: usize,
+    }
+
+    impl TryFrom<&str> for BinaryDiagnostic {
+        type Error = DiagnosticError;
+
+        /// Parses one reading per line, inferring the width of the readings from the first.
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let mut columns: Vec<Vec<u64>> = vec![];
+            let mut len = 0;
+
+            for (i, line) in value.lines().enumerate() {
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                if let Some(found) = line.chars().find(|&c| c != '0' && c != '1') {
+                    return Err(DiagnosticError::NonBinary { line: i + 1, found });
+                }
+                if len == 0 {
+                    if line.len() > MAX_WIDTH {
+                        return Err(DiagnosticError::TooWide { width: line.len() });
+                    }
+                    columns = vec![vec![]; line.len()];
+                } else if line.len() != columns.len() {
+                    return Err(DiagnosticError::RaggedLine {
+                        line: i + 1,
+                        width: line.len(),
+                        expected: columns.len(),
+                    });
+                }
+
+                let (word, bit) = (len / WORD_BITS, len % WORD_BITS);
+                for (column, digit) in columns.iter_mut().zip(line.bytes()) {
+                    if bit == 0 {
+                        column.push(0);
+                    }
+                    column[word] |= ((digit - b'0') as u64) << bit;
+                }
+                len += 1;
+            }
+
+            if len == 0 {
+                return Err(DiagnosticError::Empty);
+            }
+            let width = columns.len();
+            Ok(BinaryDiagnostic {
+                columns,
+                len,
+                width,
+            })
+        }
+    }
+
+    impl BinaryDiagnostic {
+        /// Calculates the power consumption of the submarine.
+        pub fn power_consumption(&self) -> u64 {
+            let all = self.all();
+            let mut gamma_rate = 0;
+            let mut epsilon_rate = 0;
+
+            for i in 0..self.width {
+                let (g, e) = match self.most_common_bit(&all, i) {
+                    Some(1) => (1, 0),
+                    Some(0) => (0, 1),
+                    _ => continue,
+                };
+                gamma_rate = gamma_rate << 1 | g;
+                epsilon_rate = epsilon_rate << 1 | e;
+            }
+
+            gamma_rate * epsilon_rate
+        }
+
+        /// Calculates the life support rating of the submarine.
+        pub fn life_support_rating(&self) -> u64 {
+            let oxygen_generator_rating = self.get_rating(true);
+            let co2_scrubber_rating = self.get_rating(false);
+            oxygen_generator_rating * co2_scrubber_rating
+        }
+
+        /// Returns a mask selecting every reading.
+        fn all(&self) -> Vec<u64> {
+            let mut mask = vec![u64::MAX; self.len.div_ceil(WORD_BITS)];
+            if !self.len.is_multiple_of(WORD_BITS) {
+                *mask.last_mut().unwrap() = (1 << (self.len % WORD_BITS)) - 1;
+            }
+            mask
+        }
+
+        /// Calculates the most common bit at `position` among the readings selected by
+        /// `mask`. Returns `Some(1)` if `1` is most common, `Some(0)` if `0` is most common,
+        /// and `None` if `1` and `0` are equally common or `position` is beyond the width
+        /// of the readings.
+        fn most_common_bit(&self, mask: &[u64], position: usize) -> Option<u8> {
+            let column = self.columns.get(position)?;
+            let selected = count(mask.iter().copied());
+            let ones = count(column.iter().zip(mask).map(|(c, m)| c & m));
+
+            match (2 * ones).cmp(&selected) {
+                Ordering::Equal => None,
+                Ordering::Greater => Some(1),
+                Ordering::Less => Some(0),
+            }
+        }
+
+        /// Gets a rating for the submarine. If `use_most_common` is `true`, returns
+        /// the oxygen generator rating, else returns the CO2 scrubber rating.
+        fn get_rating(&self, use_most_common: bool) -> u64 {
+            let mut mask = self.all();
+
+            for i in 0..self.width {
+                let most_common = self.most_common_bit(&mask, i).unwrap_or(1);
+                let keep_bit = if use_most_common {
+                    most_common
+                } else {
+                    1 - most_common
+                };
+
+                let kept: Vec<u64> = self.columns[i]
+                    .iter()
+                    .zip(&mask)
+                    .map(|(&c, &m)| if keep_bit == 1 { c & m } else { !c & m })
+                    .collect();
+                // Identical readings all share the bit, so none would be kept
+                if count(kept.iter().copied()) == 0 {
+                    continue;
+                }
+                mask = kept;
+
+                if count(mask.iter().copied()) == 1 {
+                    break;
+                }
+            }
+
+            // Rate the first remaining reading
+            let (word, bits) = mask
+                .iter()
+                .enumerate()
+                .find(|(_, &bits)| bits != 0)
+                .unwrap();
+            let bit = bits.trailing_zeros();
+            self.columns
+                .iter()
+                .fold(0, |rating, column| rating << 1 | (column[word] >> bit & 1))
+        }
+    }
+
+    /// Returns the number of set bits in `words`.
+    fn count(words: impl Iterator<Item = u64>) -> usize {
+        words.map(|word| word.count_ones() as usize).sum()
+    }
+}
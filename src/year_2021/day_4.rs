@@ -1,14 +1,21 @@
-use bingo::Game;
-
-pub fn main() {
-    println!("### day 4 ###");
-
-    Game::from_file("./day_4.txt").play();
+pub use bingo::{Game, Winner};
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let winners = Game::from(input).play();
+    let winner = match part {
+        1 => winners.first(),
+        2 => winners.last(),
+        _ => return Err(crate::util::unknown_part(part)),
+    };
+    winner
+        .map(|w| (w.score * w.number).to_string())
+        .ok_or_else(|| io::Error::other("no board wins the game"))
 }
 
 mod bingo {
     use std::collections::HashSet;
-    use std::fs;
 
     /// The number of entries per row and column on the bingo board.
     const BOARD_LENGTH: usize = 5;
@@ -78,22 +85,32 @@ mod bingo {
         boards: Vec<Board>,
     }
 
-    impl Game {
-        /// Constructs a bingo game from the file at `path`.
+    /// A board that won the bingo game.
+    #[derive(Debug)]
+    pub struct Winner {
+        /// Index of the winning board.
+        pub board: usize,
+        /// Score of the winning board.
+        pub score: u32,
+        /// The number that made the board win.
+        pub number: u32,
+    }
+
+    impl From<&str> for Game {
+        /// Constructs a bingo game from the string slice `value`.
         ///
         /// # Panics
         ///
-        /// Panics if there is a problem opening or reading the file.
-        pub fn from_file(path: &str) -> Game {
-            let contents = fs::read_to_string(path).unwrap();
-            let lines = contents
+        /// Panics if `value` is not a valid bingo game.
+        fn from(value: &str) -> Self {
+            let lines = value
                 .lines()
                 .map(|s| s.trim())
                 .filter(|&s| !s.is_empty())
                 .collect::<Vec<_>>();
 
             let numbers = lines
-                .get(0)
+                .first()
                 .unwrap()
                 .split(',')
                 .map(|s| s.parse::<u32>().unwrap())
@@ -122,29 +139,32 @@ mod bingo {
 
             Game { numbers, boards }
         }
+    }
 
-        /// Plays the bingo game.
-        pub fn play(mut self) {
+    impl Game {
+        /// Plays the bingo game, returning the winning boards in the order they won.
+        pub fn play(mut self) -> Vec<Winner> {
             let mut winners = HashSet::with_capacity(self.boards.len());
+            let mut results = Vec::with_capacity(self.boards.len());
 
             for num in self.numbers {
                 for (i, board) in self.boards.iter_mut().enumerate() {
-                    if winners.get(&i).is_none() {
+                    if !winners.contains(&i) {
                         board.mark(num);
 
                         if let Some(winner_score) = board.winner() {
-                            println!(
-                                "board: {}, winner score: {}, final number: {}, product: {}",
-                                i,
-                                winner_score,
-                                num,
-                                winner_score * num
-                            );
+                            results.push(Winner {
+                                board: i,
+                                score: winner_score,
+                                number: num,
+                            });
                             winners.insert(i);
                         }
                     }
                 }
             }
+
+            results
         }
     }
 }
@@ -1,23 +1,20 @@
 use hydrothermal::Vents;
-
-pub fn main() {
-    println!("### day 5 ###");
-
-    let vents = Vents::from_file("./day_5.txt").expect("could not read input file");
-
-    // Part 1
-    println!("part 1: {}", vents.overlapping(false));
-
-    // Part 2
-    println!("part 2: {}", vents.overlapping(true));
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let vents = Vents::try_from(input)?;
+    match part {
+        1 => Ok(vents.overlapping(false).to_string()),
+        2 => Ok(vents.overlapping(true).to_string()),
+        _ => Err(crate::util::unknown_part(part)),
+    }
 }
 
 mod hydrothermal {
     use std::cmp::Ordering;
     use std::collections::HashMap;
-    use std::fs;
     use std::io;
-    use std::iter;
 
     /// The length/width of the submarine's hydrothermal vent map.
     const MAP_SIZE: usize = 1000;
@@ -56,28 +53,21 @@ mod hydrothermal {
     #[derive(Debug)]
     pub struct Vents(Vec<Vent>);
 
-    impl Vents {
-        /// Parses hydrothermal vents from the file at `path`.
-        pub fn from_file(path: &str) -> io::Result<Vents> {
+    impl TryFrom<&str> for Vents {
+        type Error = io::Error;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
             let mut vents = Vents(vec![]);
-            let contents = fs::read_to_string(path)?;
 
-            for line in contents.lines() {
+            for line in value.lines() {
                 let pairs = line.splitn(2, " -> ").collect::<Vec<&str>>();
 
                 let (p1, p2) = match pairs.get(0..2) {
                     Some(rng) => (
-                        Point::try_from(rng[0])
-                            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?,
-                        Point::try_from(rng[1])
-                            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?,
+                        Point::try_from(rng[0]).map_err(io::Error::other)?,
+                        Point::try_from(rng[1]).map_err(io::Error::other)?,
                     ),
-                    None => {
-                        return Err(io::Error::new(
-                            io::ErrorKind::Other,
-                            "want two points to construct vent",
-                        ))
-                    }
+                    None => return Err(io::Error::other("want two points to construct vent")),
                 };
 
                 vents.0.push(Vent { p1, p2 })
@@ -85,7 +75,9 @@ mod hydrothermal {
 
             Ok(vents)
         }
+    }
 
+    impl Vents {
         /// Returns the number of points on the map where at least two vents overlap.
         /// Only vents that are rows and columns are considered, unless `consider_diagonals` is `true`.
         pub fn overlapping(&self, consider_diagonals: bool) -> u32 {
@@ -115,7 +107,7 @@ mod hydrothermal {
         /// Returns an iterator from `first` to `second`.
         fn get_range(first: usize, second: usize) -> Box<dyn Iterator<Item = usize>> {
             match first.cmp(&second) {
-                Ordering::Equal => Box::new(iter::repeat(first).take(MAP_SIZE)),
+                Ordering::Equal => Box::new(std::iter::repeat_n(first, MAP_SIZE)),
                 Ordering::Less => Box::new(first..second + 1),
                 Ordering::Greater => Box::new((second..first + 1).rev()),
             }
@@ -1,4 +1,4 @@
-use std::fs;
+use std::io;
 
 /// Number of days to simulate for part 1.
 const DAYS_PART_1: u32 = 80;
@@ -9,27 +9,22 @@ const DAYS_PART_2: u32 = 256;
 /// Threads to solve the problem.
 const THREADS: usize = 6;
 
-pub fn main() {
-    let contents = fs::read_to_string("./day_6.txt").expect("could not read input file");
-    let lanternfish: Vec<u32> = contents
-        .lines()
-        .map(|l| l.split(',').map(|s| s.parse::<u32>().unwrap_or(0)))
-        .flatten()
-        .collect();
-
-    // Part 1
-    println!(
-        "after {} days, there are {} lanternfish",
-        DAYS_PART_1,
-        lanternfish::get_school(&lanternfish, DAYS_PART_1, THREADS)
-    );
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let days = match part {
+        1 => DAYS_PART_1,
+        2 => DAYS_PART_2,
+        _ => return Err(crate::util::unknown_part(part)),
+    };
+    Ok(lanternfish::get_school(&parse_school(input), days, THREADS).to_string())
+}
 
-    // Part 2
-    println!(
-        "after {} days, there are {} lanternfish",
-        DAYS_PART_2,
-        lanternfish::get_school(&lanternfish, DAYS_PART_2, THREADS)
-    );
+/// Parses the initial states of a school of lanternfish from `input`.
+fn parse_school(input: &str) -> Vec<u32> {
+    input
+        .lines()
+        .flat_map(|l| l.split(',').map(|s| s.parse::<u32>().unwrap_or(0)))
+        .collect()
 }
 
 mod lanternfish {
@@ -46,10 +41,9 @@ mod lanternfish {
     /// using `threads` to perform the calculation concurrently.
     pub fn get_school(initial_school: &[u32], days: u32, threads: usize) -> u64 {
         let mut new_fish = 0;
-        for (i, chunk) in initial_school.chunks(threads).enumerate() {
+        for chunk in initial_school.chunks(threads) {
             let mut handles = vec![];
-            for (j, fish) in chunk.to_owned().into_iter().enumerate() {
-                println!("fish {}", threads * i + j);
+            for fish in chunk.iter().copied() {
                 handles.push(thread::spawn(move || get_offspring(fish, days)));
             }
             new_fish += handles.into_iter().map(|h| h.join().unwrap()).sum::<u64>();
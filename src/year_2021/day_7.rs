@@ -1,33 +1,35 @@
 use std::cmp::Ordering;
-use std::fs;
+use std::io;
 
-pub fn main() {
-    println!("### day 7 ###");
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let crabs = parse_crabs(input);
+    match part {
+        1 => Ok(min_fuel(&crabs, Crab::fuel_constant).to_string()),
+        2 => Ok(min_fuel(&crabs, Crab::fuel_linear).to_string()),
+        _ => Err(crate::util::unknown_part(part)),
+    }
+}
 
-    let input = fs::read_to_string("./day_7.txt").expect("could not read input file");
-    let crabs: Vec<Crab> = input
+/// Parses the positions of the crab submarines from `input`.
+fn parse_crabs(input: &str) -> Vec<Crab> {
+    input
         .lines()
-        .map(|l| {
+        .flat_map(|l| {
             l.split(',')
                 .map(|s| s.parse::<u32>().unwrap_or(0))
                 .map(Crab::new)
         })
-        .flatten()
-        .collect();
-
-    // Part 1
-    let mut fuel: Vec<u32> = vec![];
-    for x in MIN_POSITION..=MAX_POSITION {
-        fuel.push(crabs.iter().map(|c| c.fuel_constant(x)).sum());
-    }
-    println!("part 1: {}", fuel.into_iter().min().unwrap());
+        .collect()
+}
 
-    // Part 2
-    let mut fuel: Vec<u32> = vec![];
+/// Returns the minimum total fuel for all `crabs` to align on one position, given a `fuel` rate.
+fn min_fuel(crabs: &[Crab], fuel: fn(&Crab, u32) -> u32) -> u32 {
+    let mut totals: Vec<u32> = vec![];
     for x in MIN_POSITION..=MAX_POSITION {
-        fuel.push(crabs.iter().map(|c| c.fuel_linear(x)).sum())
+        totals.push(crabs.iter().map(|c| fuel(c, x)).sum());
     }
-    println!("part 2: {}", fuel.into_iter().min().unwrap());
+    totals.into_iter().min().unwrap()
 }
 
 /// Minimum horizontal position.
@@ -1,11 +1,20 @@
 use std::collections::{HashMap, HashSet};
-use std::fs;
+use std::io;
 
-pub fn main() {
-    println!("### day 8 ###");
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let lines = parse_lines(input);
+    match part {
+        1 => Ok(part_1(&lines).to_string()),
+        2 => Ok(part_2(&lines).to_string()),
+        _ => Err(crate::util::unknown_part(part)),
+    }
+}
 
-    let input = fs::read_to_string("./day_8.txt").expect("could not read input file");
-    let lines: Vec<_> = input
+/// Parses each line of `input` into its signal patterns and output digits, with the
+/// segments of each pattern and digit sorted.
+fn parse_lines(input: &str) -> Vec<(Vec<String>, Vec<String>)> {
+    input
         .lines()
         .map(|line| line.split(" | "))
         .map(|mut pieces| {
@@ -31,13 +40,7 @@ pub fn main() {
 
             (patterns, digits)
         })
-        .collect();
-
-    // Part 1
-    println!("digits 1, 4, 7, and 8 appear {} times", part_1(&lines));
-
-    // Part 2
-    println!("sum of all numbers is {}", part_2(&lines));
+        .collect()
 }
 
 /// Solution for part 1.
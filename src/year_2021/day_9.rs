@@ -1,27 +1,20 @@
 use heightmap::HeightMap;
-use std::path::Path;
-
-pub fn main() {
-    println!("### day 9 ###");
-
-    let input_path = Path::new("./day_9.txt");
-    let height_map = HeightMap::from_file(input_path).expect("could not read input file");
-
-    // Part 1
-    println!("part 1: risk level = {}", height_map.risk_level());
-
-    // Part 2
-    println!(
-        "part 2: three largest basins product = {}",
-        height_map.three_largest_basins_product()
-    );
+use std::io;
+
+/// Solves `part` of the puzzle for `input`.
+pub fn solve(input: &str, part: u8) -> io::Result<String> {
+    let height_map = HeightMap::try_from(input)?;
+    match part {
+        1 => Ok(height_map.risk_level().to_string()),
+        2 => Ok(height_map.three_largest_basins_product().to_string()),
+        _ => Err(crate::util::unknown_part(part)),
+    }
 }
 
 mod heightmap {
+    use crate::util;
     use std::collections::{HashMap, HashSet};
-    use std::fs;
     use std::io;
-    use std::path::Path;
 
     /// Width/height of the lava tube map.
     const MAP_SIZE: usize = 100;
@@ -30,29 +23,15 @@ mod heightmap {
     #[derive(Debug)]
     pub struct HeightMap(HashMap<(usize, usize), u32>);
 
-    impl HeightMap {
-        /// Constructs a lava tube map from a file.
-        pub fn from_file(path: &Path) -> io::Result<HeightMap> {
-            let mut height_map = HashMap::with_capacity(MAP_SIZE * MAP_SIZE);
-
-            let contents = fs::read_to_string(path)?;
-            for (x, line) in contents.lines().enumerate() {
-                for (y, c) in line.chars().enumerate() {
-                    height_map.insert(
-                        (x, y),
-                        c.to_digit(10).ok_or_else(|| {
-                            io::Error::new(
-                                io::ErrorKind::Other,
-                                format!("could not parse digit: {}", c),
-                            )
-                        })?,
-                    );
-                }
-            }
+    impl TryFrom<&str> for HeightMap {
+        type Error = io::Error;
 
-            Ok(HeightMap(height_map))
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            Ok(HeightMap(util::digit_grid(value)?))
         }
+    }
 
+    impl HeightMap {
         /// Calculates the total risk level of the lava tube height map.
         pub fn risk_level(&self) -> u32 {
             let mut risk_level = 0;
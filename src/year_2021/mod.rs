@@ -0,0 +1,61 @@
+//! Advent of Code 2021.
+
+use crate::Solver;
+
+pub mod day_1;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_2;
+pub mod day_20;
+pub mod day_21;
+pub mod day_22;
+pub mod day_23;
+pub mod day_24;
+pub mod day_25;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+
+/// Returns the solver for `day`, or `None` if `day` has not been solved.
+pub fn solver(day: u8) -> Option<Solver> {
+    match day {
+        1 => Some(day_1::solve),
+        2 => Some(day_2::solve),
+        3 => Some(day_3::solve),
+        4 => Some(day_4::solve),
+        5 => Some(day_5::solve),
+        6 => Some(day_6::solve),
+        7 => Some(day_7::solve),
+        8 => Some(day_8::solve),
+        9 => Some(day_9::solve),
+        10 => Some(day_10::solve),
+        11 => Some(day_11::solve),
+        12 => Some(day_12::solve),
+        13 => Some(day_13::solve),
+        14 => Some(day_14::solve),
+        15 => Some(day_15::solve),
+        16 => Some(day_16::solve),
+        17 => Some(day_17::solve),
+        18 => Some(day_18::solve),
+        19 => Some(day_19::solve),
+        20 => Some(day_20::solve),
+        21 => Some(day_21::solve),
+        22 => Some(day_22::solve),
+        23 => Some(day_23::solve),
+        24 => Some(day_24::solve),
+        25 => Some(day_25::solve),
+        _ => None,
+    }
+}
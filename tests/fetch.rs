@@ -0,0 +1,111 @@
+use advent_of_code::input::Fetcher;
+use advent_of_code::Puzzle;
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// A stand-in for the Advent of Code website, serving `body` for every request and
+/// recording the request line and cookie of each request it receives.
+struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<(String, String)>>>,
+}
+
+impl MockServer {
+    fn start(status: &'static str, body: &'static str) -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+
+        let recorded = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+
+                let mut cookie = String::new();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':') {
+                        if name.eq_ignore_ascii_case("cookie") {
+                            cookie = value.trim().to_owned();
+                        }
+                    }
+                }
+
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push((request_line.trim().to_owned(), cookie));
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        MockServer { url, requests }
+    }
+
+    fn requests(&self) -> Vec<(String, String)> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Returns an empty cache directory unique to `name`.
+fn cache_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn fetch_downloads_with_session_and_caches() {
+    let server = MockServer::start("200 OK", "199\n200\n208\n");
+    let dir = cache_dir("caches");
+    let fetcher = Fetcher::new(&server.url, "secret", &dir);
+
+    let path = fetcher.fetch(Puzzle::new(2021, 1)).unwrap();
+    assert_eq!(path, dir.join("2021").join("day_1.txt"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "199\n200\n208\n");
+    assert_eq!(
+        server.requests(),
+        vec![(
+            "GET /2021/day/1/input HTTP/1.1".to_owned(),
+            "session=secret".to_owned()
+        )]
+    );
+
+    // A cached input is never downloaded again
+    assert_eq!(fetcher.fetch(Puzzle::new(2021, 1)).unwrap(), path);
+    assert_eq!(server.requests().len(), 1);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn fetch_does_not_cache_failed_downloads() {
+    let server = MockServer::start("404 Not Found", "not found");
+    let dir = cache_dir("fails");
+    let fetcher = Fetcher::new(&server.url, "secret", &dir);
+
+    assert!(fetcher.fetch(Puzzle::new(2021, 2)).is_err());
+    assert!(!dir.join("2021").join("day_2.txt").exists());
+    assert_eq!(server.requests().len(), 1);
+}
@@ -0,0 +1,288 @@
+use advent_of_code::year_2021::*;
+
+const DAY_1_EXAMPLE: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+
+#[test]
+fn day_1_example_has_no_anomalies() {
+    let depth = day_1::Depth::try_from(DAY_1_EXAMPLE).unwrap();
+    for method in [
+        day_1::AnomalyMethod::MedianAbsoluteDeviation,
+        day_1::AnomalyMethod::ZScore,
+    ] {
+        let detector = day_1::AnomalyDetector {
+            method,
+            ..Default::default()
+        };
+        assert_eq!(depth.anomalies(&detector), [], "{:?}", method);
+    }
+}
+
+#[test]
+fn day_1_spike_is_an_anomaly() {
+    let depth = day_1::Depth::try_from("100\n100\n101\n100\n100\n5000\n100\n101\n100").unwrap();
+    for method in [
+        day_1::AnomalyMethod::MedianAbsoluteDeviation,
+        day_1::AnomalyMethod::ZScore,
+    ] {
+        let detector = day_1::AnomalyDetector {
+            method,
+            ..Default::default()
+        };
+        assert_eq!(depth.anomalies(&detector), [5], "{:?}", method);
+    }
+}
+
+#[test]
+fn day_1_even_window_has_extra_neighbour_after() {
+    // With a window of 2, each reading is only compared with the one after it
+    let depth = day_1::Depth::try_from("100\n500\n100").unwrap();
+    let detector = day_1::AnomalyDetector {
+        window: 2,
+        ..Default::default()
+    };
+    assert_eq!(depth.anomalies(&detector), [0, 1]);
+}
+
+#[test]
+fn day_2_overflow_is_a_navigation_error() {
+    let input = "down 4000000000\nforward 4000000000\n";
+    let directions = day_2::Directions::try_from(input).unwrap();
+    assert_eq!(
+        directions.position(true),
+        Err(day_2::NavigationError::Overflow { index: 1 })
+    );
+    assert!(day_2::solve(input, 1).is_err());
+    assert!(day_2::solve(input, 2).is_err());
+}
+
+#[test]
+fn day_2_script() {
+    let script = "\
+        let d = 5 # step length
+        repeat 2 {
+            forward d
+            down 2
+        }
+        back 3
+        repeat 0 { up 1 }
+        surface
+    ";
+    let directions = day_2::Directions::compile(script).unwrap();
+    assert_eq!(
+        directions.to_string(),
+        "forward 5\ndown 2\nforward 5\ndown 2\nback 3\nsurface\n"
+    );
+}
+
+/// Returns the line and message of the error compiling `script`.
+fn day_2_script_error(script: &str) -> (usize, String) {
+    match day_2::Directions::compile(script) {
+        Ok(_) => panic!("{:?} compiled", script),
+        Err(error) => (error.line, error.message),
+    }
+}
+
+#[test]
+fn day_2_script_errors() {
+    let cases = [
+        ("forward 1\nsideways 2", 2, "unknown command: sideways"),
+        ("repeat 2 {\nforward 1\n", 1, "repeat block is never closed"),
+        ("forward 1\n}", 2, "unexpected '}'"),
+        ("repeat -1 { forward 1 }", 1, "invalid value: -1"),
+        ("repeat n { forward 1 }", 1, "undefined constant: n"),
+        ("repeat 2 forward 1", 1, "expected '{', found forward"),
+        ("forward\n", 1, "expected a value"),
+    ];
+    for (script, line, message) in cases {
+        assert_eq!(day_2_script_error(script), (line, message.to_string()));
+    }
+}
+
+#[test]
+fn day_2_script_repeat_limit() {
+    let message = "repeat expands to more than 1048576 commands".to_string();
+    assert_eq!(
+        day_2_script_error("forward 1\nrepeat 4000000000 { forward 1 }"),
+        (2, message.clone())
+    );
+    assert_eq!(
+        day_2_script_error("repeat 1024 {\n  repeat 1024 {\n    repeat 2 { up 1 }\n  }\n}"),
+        (1, message)
+    );
+    // Exactly at the limit is fine
+    let directions = day_2::Directions::compile("repeat 1024 { repeat 1024 { up 1 } }").unwrap();
+    assert_eq!(directions.position(false).unwrap().depth, -(1 << 20));
+}
+
+/// Converts a string of `0`s and `1`s into hexadecimal, padding it with zeros to whole digits.
+fn bits_to_hex(bits: &str) -> String {
+    let mut bits = bits.to_string();
+    while !bits.len().is_multiple_of(4) {
+        bits.push('0');
+    }
+    bits.as_bytes()
+        .chunks(4)
+        .map(|digit| {
+            let digit = u32::from_str_radix(std::str::from_utf8(digit).unwrap(), 2).unwrap();
+            char::from_digit(digit, 16).unwrap().to_ascii_uppercase()
+        })
+        .collect()
+}
+
+/// Encodes `value` as a version 0 literal packet, in binary, using `groups` groups.
+fn literal_bits(value: u128, groups: u32) -> String {
+    let mut bits = "000100".to_string();
+    for group in (0..groups).rev() {
+        let more = if group == 0 { '0' } else { '1' };
+        bits.push(more);
+        bits.push_str(&format!("{:04b}", (value >> (4 * group)) & 0b1111));
+    }
+    bits
+}
+
+/// Encodes a version 0 operator packet with type ID `type_id`, in binary, around the
+/// binary `packets`.
+fn operator_bits(type_id: u8, packets: &[String]) -> String {
+    format!(
+        "000{:03b}1{:011b}{}",
+        type_id,
+        packets.len(),
+        packets.concat()
+    )
+}
+
+#[test]
+fn day_16_examples() {
+    let packet = day_16::Packet::from_hex("9C0141080250320F1802104A08").unwrap();
+    assert_eq!(packet.evaluate(), Ok(1));
+    let packet = day_16::Packet::from_hex("A0016C880162017C3686B18A3D4780").unwrap();
+    assert_eq!(packet.version_sum(), 31);
+}
+
+#[test]
+fn day_16_literal_of_up_to_64_bits() {
+    // 17 groups are fine when the first one is zero
+    let hex = bits_to_hex(&literal_bits(u64::MAX as u128, 17));
+    let packet = day_16::Packet::from_hex(&hex).unwrap();
+    assert_eq!(packet.evaluate(), Ok(u64::MAX));
+}
+
+#[test]
+fn day_16_literal_too_large() {
+    let hex = bits_to_hex(&literal_bits(1 << 64, 17));
+    assert_eq!(
+        day_16::Packet::from_hex(&hex),
+        Err(day_16::PacketError::LiteralTooLarge { position: 6 })
+    );
+}
+
+#[test]
+fn day_16_sum_and_product_overflow() {
+    let max = literal_bits(u64::MAX as u128, 16);
+    let sum = day_16::Packet::from_hex(&bits_to_hex(&operator_bits(0, &[max.clone(), max])));
+    assert_eq!(
+        sum.unwrap().evaluate(),
+        Err(day_16::PacketError::Overflow(day_16::Operator::Sum))
+    );
+
+    let half = literal_bits(1 << 32, 9);
+    let product = day_16::Packet::from_hex(&bits_to_hex(&operator_bits(1, &[half.clone(), half])));
+    assert_eq!(
+        product.unwrap().evaluate(),
+        Err(day_16::PacketError::Overflow(day_16::Operator::Product))
+    );
+}
+
+#[test]
+fn day_16_operator_without_sub_packets() {
+    for (type_id, operator) in [
+        (0, day_16::Operator::Sum),
+        (2, day_16::Operator::Minimum),
+        (3, day_16::Operator::Maximum),
+    ] {
+        assert_eq!(
+            day_16::Packet::from_hex(&bits_to_hex(&operator_bits(type_id, &[]))),
+            Err(day_16::PacketError::InvalidOperandCount { operator, count: 0 })
+        );
+    }
+}
+
+#[test]
+fn day_17_example() {
+    let target = day_17::Target::try_from("target area: x=20..30, y=-10..-5").unwrap();
+    assert_eq!(target.highest_apex(), Some(45));
+    assert_eq!(target.velocities().len(), 112);
+}
+
+#[test]
+fn day_17_apex_of_hit_on_the_way_up() {
+    // Launched straight up at 5, the probe hits the target after one step, then keeps
+    // rising to 15
+    let target = day_17::Target::try_from("target area: x=0..0, y=5..5").unwrap();
+    assert_eq!(target.trajectory((0, 5)).positions, [(0, 5)]);
+    assert_eq!(target.highest_apex(), Some(15));
+}
+
+#[test]
+fn day_18_regular_number_too_large() {
+    let error = day_18::Number::try_from("[1,99999999999]").unwrap_err();
+    assert_eq!(error.to_string(), "invalid regular number at position 3");
+}
+
+#[test]
+fn day_21_quantum_wins_example() {
+    let game =
+        day_21::Game::try_from("Player 1 starting position: 4\nPlayer 2 starting position: 8\n")
+            .unwrap();
+    assert_eq!(game.quantum_wins(3, 3), [990, 207]);
+    assert_eq!(game.quantum_wins(21, 3), [444356092776315, 341960390180808]);
+}
+
+#[test]
+fn day_22_volume_beyond_64_bits() {
+    let step = "on x=-3000000..3000000,y=-3000000..3000000,z=-3000000..3000000";
+    let steps = day_22::RebootSteps::try_from(step).unwrap();
+    assert_eq!(steps.lit(), 6000001i128.pow(3));
+    assert!(steps.lit() > i64::MAX as i128);
+    assert_eq!(
+        day_22::solve(step, 2).unwrap(),
+        6000001i128.pow(3).to_string()
+    );
+}
+
+#[test]
+fn day_24_overflow_is_an_error() {
+    let program = day_24::Program::try_from("inp z\nmul z 9999999999\nmul z 9999999999").unwrap();
+    let error = day_24::Alu::default().run(&program, [1]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "line 3: mul z 9999999999: overflow: 9999999999 * 9999999999"
+    );
+
+    let program = day_24::Program::try_from("inp w\ninp x\ndiv w x").unwrap();
+    let error = day_24::Alu::default()
+        .run(&program, [i64::MIN, -1])
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        format!("line 3: div w x: overflow: {} / -1", i64::MIN)
+    );
+}
+
+#[test]
+fn day_25_example() {
+    let sea_floor = day_25::SeaFloor::try_from(
+        "v...>>.vv>\n.vv>>.vv..\n>>.>v>...v\n>>v>>.>.v.\nv>v.vv.v..\n\
+         >.>>..v...\n.vv..>.>v.\nv.v..>>v.v\n....v..v.>",
+    )
+    .unwrap();
+    assert_eq!(sea_floor.first_stationary_step(), Some(58));
+}
+
+#[test]
+fn day_25_herds_that_never_stop() {
+    let sea_floor = day_25::SeaFloor::try_from(">.\n..").unwrap();
+    assert_eq!(sea_floor.first_stationary_step(), None);
+    let sea_floor = day_25::SeaFloor::try_from(">>>.>>.\nv......\n.......").unwrap();
+    assert_eq!(sea_floor.first_stationary_step(), None);
+}